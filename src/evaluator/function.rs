@@ -59,13 +59,39 @@ impl Callable for Function {
         unreachable!("Non-fn statement passed as declaration to Function::new(declr)");
     }
 
+    fn min_arity(&self) -> usize {
+        if let StmtKind::Fn {
+            params, defaults, ..
+        } = &self.declr.kind
+        {
+            return params.len() - defaults.len();
+        }
+
+        unreachable!("Non-fn statement passed as declaration to Function::new(declr)");
+    }
+
     fn call(
         &self,
         evaluator: &mut Evaluator,
-        args: Vec<Value>,
+        mut args: Vec<Value>,
         cursor: Cursor,
     ) -> EvalResult<Value> {
-        if let StmtKind::Fn { params, body, .. } = &self.declr.kind {
+        if let StmtKind::Fn {
+            params,
+            defaults,
+            body,
+            ..
+        } = &self.declr.kind
+        {
+            // fill in the defaults of the parameters that weren't passed
+            let first_default = params.len() - defaults.len();
+            for default in defaults
+                .iter()
+                .skip(args.len().saturating_sub(first_default))
+            {
+                args.push(evaluator.eval_expr_in(default, self.closure.clone())?);
+            }
+
             let env = Env::enclosed(self.closure.clone());
 
             for (i, param) in params.iter().enumerate() {
@@ -28,7 +28,20 @@ pub struct Loader {
 }
 
 impl Loader {
-    pub fn load(self_ptr: LoaderPtr, file: PathBuf, caller_dir: &Path) -> EvalResult<EnvPtr> {
+    /// Mark `file` as being loaded. Scripts loaded by the Loader are marked while they run, this
+    /// is for the entry script so that the scripts it uses can't use it back either
+    pub fn mark_visiting(&mut self, file: &Path) {
+        if let Ok(canonical) = file.canonicalize() {
+            self.visiting.insert(canonical);
+        }
+    }
+
+    pub fn load(
+        self_ptr: LoaderPtr,
+        file: PathBuf,
+        caller_dir: &Path,
+        args: Rc<Vec<String>>,
+    ) -> EvalResult<EnvPtr> {
         // Resolve path relative to caller and canonicalize for caching/cycle detection.
         let resolved = if file.is_absolute() {
             file
@@ -121,8 +134,8 @@ impl Loader {
                 }
             };
 
-            let mut evaluator = Evaluator::with_loader(&src, self_ptr.clone());
-            evaluator.eval()?;
+            let mut evaluator = Evaluator::with_loader(&src, self_ptr.clone(), args);
+            evaluator.eval_program()?;
 
             Ok(evaluator.globals.clone())
         })();
@@ -10,12 +10,14 @@ pub mod value;
 
 use std::{
     cell::RefCell,
+    cmp::Ordering,
     collections::HashMap,
     panic::{AssertUnwindSafe, catch_unwind},
     path::{Path, PathBuf},
     rc::Rc,
 };
 
+use indexmap::IndexMap;
 use ordered_float::OrderedFloat;
 
 use crate::{
@@ -29,7 +31,7 @@ use crate::{
         runtime_err::{ErrKind, EvalResult, RuntimeErr, RuntimeEvent},
         value::{Callable, Value, ValueKey},
     },
-    lexer::token::KeywordKind,
+    lexer::{cursor::Cursor, token::KeywordKind},
     parser::{
         expr::{AssignOp, BinaryOp, Expr, ExprKind, LiteralType, LogicalOp, UnaryOp},
         stmt::{Stmt, StmtKind},
@@ -45,6 +47,10 @@ pub struct Evaluator<'a> {
     env: EnvPtr,
     prototypes: ValuePrototypes,
     loader: LoaderPtr,
+    /// Command line arguments passed after the program file
+    pub args: Rc<Vec<String>>,
+    /// Print the program's global variables after each top-level statement
+    pub trace: bool,
 }
 
 impl<'a> Evaluator<'a> {
@@ -58,37 +64,141 @@ impl<'a> Evaluator<'a> {
             env: Env::new(),
             prototypes: ValuePrototypes::new(),
             loader: Rc::new(RefCell::new(Loader::default())),
+            args: Rc::new(Vec::new()),
+            trace: false,
         };
         this.env = this.globals.clone();
         this
     }
 
-    pub fn with_loader(src: &'a Src, loader: LoaderPtr) -> Self {
+    pub fn with_loader(src: &'a Src, loader: LoaderPtr, args: Rc<Vec<String>>) -> Self {
         let mut evaluator = Evaluator::new(src);
         evaluator.loader = loader;
+        evaluator.args = args;
         evaluator
     }
 
+    pub fn with_args(src: &'a Src, args: Vec<String>) -> Self {
+        let mut evaluator = Evaluator::new(src);
+        evaluator.args = Rc::new(args);
+        evaluator
+    }
+
+    /// Create an evaluator that runs on top of an existing global environment, used by the
+    /// REPL to keep definitions around between inputs
+    pub fn with_globals(src: &'a Src, globals: EnvPtr) -> Self {
+        let mut evaluator = Evaluator::new(src);
+        evaluator.globals = globals.clone();
+        evaluator.env = globals;
+        evaluator
+    }
+
+    /// Run the program, errors are reported before being returned. A halt() ends it early
+    /// without an error and restores the terminal if the TUI was in use
     pub fn eval(&mut self) -> EvalResult<()> {
+        match self.eval_program() {
+            Err(RuntimeEvent::Halt) => {
+                natives::reset_tui();
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Same as `eval` but a halt() is returned as `RuntimeEvent::Halt`, so programs loaded with
+    /// `use` can stop the one that loaded them
+    pub(crate) fn eval_program(&mut self) -> EvalResult<()> {
+        let natives = if self.trace {
+            self.native_values()
+        } else {
+            HashMap::new()
+        };
+
         for stmt in self.ast.clone().iter() {
-            match self.eval_stmt(stmt) {
-                Ok(_) => {}
+            if let Err(err) = self.eval_stmt(stmt) {
+                self.report_err(&err);
+                return Err(err);
+            }
+            if self.trace {
+                eprintln!("[trace] line {}", stmt.cursor.line + 1);
+                for line in self.trace_lines(&natives) {
+                    eprintln!("  {}", line);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The globals before the program runs, at that point they're all natives
+    fn native_values(&self) -> HashMap<String, Value> {
+        self.globals.borrow().entries().into_iter().collect()
+    }
+
+    /// `name = value` lines for every global the program defined, sorted by name. Natives are
+    /// left out unless the program redefined them
+    fn trace_lines(&self, natives: &HashMap<String, Value>) -> Vec<String> {
+        let mut entries: Vec<(String, Value)> = self
+            .globals
+            .borrow()
+            .entries()
+            .into_iter()
+            .filter(|(name, val)| !natives.get(name).is_some_and(|native| native.is_same(val)))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        entries
+            .into_iter()
+            .map(|(name, val)| {
+                let val_str = match &val {
+                    Value::Callable(c) => format!("<fn {}>", c.name()),
+                    other => other.to_quoted_string(),
+                };
+                format!("{} = {}", name, val_str)
+            })
+            .collect()
+    }
+
+    /// Same as `eval`, but if the last statement is an expression its value is returned
+    pub fn eval_repl(&mut self) -> EvalResult<Option<Value>> {
+        let ast = self.ast.clone();
+        for (i, stmt) in ast.iter().enumerate() {
+            let result = match &stmt.kind {
+                StmtKind::Expr(expr) if i == ast.len() - 1 => self.eval_expr(expr).map(Some),
+                _ => self.eval_stmt(stmt).map(|_| None),
+            };
+            match result {
+                Ok(Some(val)) => return Ok(Some(val)),
+                Ok(None) => {}
+                Err(RuntimeEvent::Halt) => {
+                    natives::reset_tui();
+                    return Ok(None);
+                }
                 Err(err) => {
-                    if let RuntimeEvent::Err(RuntimeErr {
-                        kind, msg, cursor, ..
-                    }) = &err
-                    {
-                        Reporter::error_at(msg, kind.to_string(), self.src, *cursor);
-                    }
-                    if let RuntimeEvent::UserErr { val, cursor } = &err {
-                        let msg = format!("user error: {}", val);
-                        Reporter::error_at(msg.as_str(), "UserErr".into(), self.src, *cursor);
-                    }
+                    self.report_err(&err);
                     return Err(err);
                 }
             }
         }
-        Ok(())
+        Ok(None)
+    }
+
+    fn report_err(&self, err: &RuntimeEvent) {
+        if let RuntimeEvent::Err(RuntimeErr {
+            kind,
+            msg,
+            cursor,
+            note,
+        }) = err
+        {
+            Reporter::error_at(msg, kind.to_string(), self.src, *cursor);
+            if let Some(note) = note {
+                Reporter::note(note);
+            }
+        }
+        if let RuntimeEvent::UserErr { val, cursor } = err {
+            let msg = format!("user error: {}", val);
+            Reporter::error_at(msg.as_str(), "UserErr".into(), self.src, *cursor);
+        }
     }
 
     // Statement functions
@@ -129,8 +239,14 @@ impl<'a> Evaluator<'a> {
 
             // Resolve relative to current source file.
             let caller_dir = self.src.file.parent().unwrap_or_else(|| Path::new("."));
-
-            match Loader::load(self.loader.clone(), PathBuf::from(path_str), caller_dir) {
+            self.loader.borrow_mut().mark_visiting(&self.src.file);
+
+            match Loader::load(
+                self.loader.clone(),
+                PathBuf::from(&path_str),
+                caller_dir,
+                self.args.clone(),
+            ) {
                 Ok(env) => {
                     // Merge imported globals into our globals.
                     for (name, value) in env.borrow().entries() {
@@ -139,10 +255,20 @@ impl<'a> Evaluator<'a> {
 
                     return Ok(());
                 }
+                // Keep the reason (missing file, circular use...) as a note
+                Err(RuntimeEvent::Err(err)) => {
+                    return Err(RuntimeEvent::error_with_note(
+                        err.kind,
+                        format!("failed to load '{}'", path_str),
+                        err.msg,
+                        stmt.cursor,
+                    ));
+                }
+                Err(RuntimeEvent::Halt) => return Err(RuntimeEvent::Halt),
                 Err(_) => {
                     return Err(RuntimeEvent::error(
                         ErrKind::IO,
-                        "failed to load file".into(),
+                        format!("failed to load '{}'", path_str),
                         stmt.cursor,
                     ));
                 }
@@ -229,11 +355,10 @@ impl<'a> Evaluator<'a> {
 
             match iter {
                 Value::List(rc_list) => {
-                    let len = rc_list.borrow().len();
-
-                    for i in 0..len {
-                        let elem = rc_list.borrow()[i].clone();
+                    // iterate over a snapshot so the body can freely mutate the list
+                    let items = rc_list.borrow().clone();
 
+                    for (i, elem) in items.into_iter().enumerate() {
                         let loop_env = Env::enclosed(self.env.clone());
                         loop_env.borrow_mut().define(item.clone(), elem);
 
@@ -283,7 +408,7 @@ impl<'a> Evaluator<'a> {
 
             return Ok(());
         }
-        unreachable!("Non-if statement passed to Evaluator::eval_stmt_if");
+        unreachable!("Non-for statement passed to Evaluator::eval_stmt_for");
     }
 
     fn eval_stmt_while(&mut self, stmt: &Stmt) -> EvalResult<()> {
@@ -351,7 +476,7 @@ impl<'a> Evaluator<'a> {
                         if let Some(kind) = err_kind {
                             catch_env.borrow_mut().define(
                                 kind.clone(),
-                                Value::Str(Rc::new(RefCell::new("RuntimeErr".into()))),
+                                Value::Str(Rc::new(RefCell::new(err.kind.to_string()))),
                             );
                         }
                         if let Some(eval) = err_val {
@@ -452,6 +577,15 @@ impl<'a> Evaluator<'a> {
         unreachable!("Non-block statement passed to Evaluator::eval_stmt_block");
     }
 
+    /// Evaluates an expression in another environment, used for default parameter values which
+    /// belong to the scope their function was defined in
+    fn eval_expr_in(&mut self, expr: &Expr, env: EnvPtr) -> EvalResult<Value> {
+        let prev = std::mem::replace(&mut self.env, env);
+        let result = self.eval_expr(expr);
+        self.env = prev;
+        result
+    }
+
     // Expression functions
 
     fn eval_expr(&mut self, expr: &Expr) -> EvalResult<Value> {
@@ -462,6 +596,7 @@ impl<'a> Evaluator<'a> {
             ExprKind::Unary { .. } => self.eval_expr_unary(expr),
             ExprKind::Literal(_) => self.eval_expr_literal(expr),
             ExprKind::List(_) => self.eval_expr_list(expr),
+            ExprKind::Interp(_) => self.eval_expr_interp(expr),
             ExprKind::Dict(_) => self.eval_expr_dict(expr),
             ExprKind::Range { .. } => self.eval_expr_range(expr),
             ExprKind::Index { .. } => self.eval_expr_index(expr),
@@ -473,6 +608,7 @@ impl<'a> Evaluator<'a> {
             ExprKind::Get { .. } => self.eval_expr_get(expr),
             ExprKind::Set { .. } => self.eval_expr_set(expr),
             ExprKind::ESelf => self.lookup_var(KeywordKind::KSelf.to_string().as_str(), expr),
+            ExprKind::Lambda { .. } => self.eval_expr_lambda(expr),
         }
     }
 
@@ -484,11 +620,7 @@ impl<'a> Evaluator<'a> {
             let current = self.lookup_var(name.as_str(), expr)?;
 
             // compute new value
-            let new_val = match op {
-                AssignOp::Value => rhs_val.clone(),
-                AssignOp::Add => current.add_assign(rhs_val, expr.cursor)?,
-                AssignOp::Sub => current.sub_assign(rhs_val, expr.cursor)?,
-            };
+            let new_val = current.apply_assign(op, rhs_val, expr.cursor)?;
 
             // write back
             if let Some(d) = expr.get_resolved_dist() {
@@ -570,9 +702,20 @@ impl<'a> Evaluator<'a> {
         unreachable!("Non-list passed to Evaluator::eval_expr_list");
     }
 
+    fn eval_expr_interp(&mut self, expr: &Expr) -> EvalResult<Value> {
+        if let ExprKind::Interp(parts) = &expr.kind {
+            let mut out = String::new();
+            for part in parts {
+                out.push_str(&self.eval_expr(part)?.to_string());
+            }
+            return Ok(Value::Str(Rc::new(RefCell::new(out))));
+        }
+        unreachable!("Non-interp passed to Evaluator::eval_expr_interp");
+    }
+
     fn eval_expr_dict(&mut self, expr: &Expr) -> EvalResult<Value> {
         if let ExprKind::Dict(dict) = &expr.kind {
-            let mut map: HashMap<ValueKey, Value> = HashMap::new();
+            let mut map: IndexMap<ValueKey, Value> = IndexMap::new();
 
             for (key, value) in dict {
                 let key_val = match ValueKey::try_from(&self.eval_expr(key)?) {
@@ -679,19 +822,12 @@ impl<'a> Evaluator<'a> {
                                 })
                             })
                             .collect::<Result<_, _>>()?;
+                        // missing keys read as Null
                         let map_ref = map.borrow();
-                        let mut out = Vec::with_capacity(keys.len());
-                        for key in keys.iter() {
-                            if let Some(v) = map_ref.get(key) {
-                                out.push(v.clone());
-                            } else {
-                                return Err(RuntimeEvent::error(
-                                    ErrKind::Value,
-                                    "dict index not found".into(),
-                                    expr.cursor,
-                                ));
-                            }
-                        }
+                        let out = keys
+                            .iter()
+                            .map(|key| map_ref.get(key).cloned().unwrap_or(Value::Null))
+                            .collect();
                         Ok(Value::List(Rc::new(RefCell::new(out))))
                     }
                     _ => {
@@ -702,61 +838,36 @@ impl<'a> Evaluator<'a> {
                                 index.cursor,
                             )
                         })?;
-                        if let Some(v) = map.borrow().get(&key) {
-                            Ok(v.clone())
-                        } else {
-                            Err(RuntimeEvent::error(
-                                ErrKind::Value,
-                                "dict index not found".into(),
-                                expr.cursor,
-                            ))
-                        }
+                        Ok(map.borrow().get(&key).cloned().unwrap_or(Value::Null))
                     }
                 },
                 Value::List(rc_items) => match index_val {
-                    Value::Num(n) => {
-                        let idx = n.0 as usize;
+                    Value::Num(_) => {
                         let items = rc_items.borrow();
-                        if idx >= items.len() {
-                            return Err(RuntimeEvent::error(
-                                ErrKind::Value,
-                                format!("list index {} out of bounds (len = {})", idx, items.len()),
-                                expr.cursor,
-                            ));
-                        }
+                        let idx = index_val.check_index(
+                            items.len(),
+                            index.cursor,
+                            Some("list index".into()),
+                        )?;
                         Ok(items[idx].clone())
                     }
                     Value::List(idx_list) => {
-                        let indices: Vec<usize> = idx_list
-                            .borrow()
-                            .iter()
-                            .map(|v| {
-                                if let Value::Num(n) = v {
-                                    Ok(n.0 as usize)
-                                } else {
-                                    Err(RuntimeEvent::error(
-                                        ErrKind::Type,
-                                        "index list must contain only Num values".into(),
-                                        index.cursor,
-                                    ))
-                                }
-                            })
-                            .collect::<Result<_, _>>()?;
                         let items = rc_items.borrow();
-                        let mut out = Vec::with_capacity(indices.len());
-                        for i in indices.iter() {
-                            if *i >= items.len() {
+                        let mut out = Vec::with_capacity(idx_list.borrow().len());
+                        for v in idx_list.borrow().iter() {
+                            if !matches!(v, Value::Num(_)) {
                                 return Err(RuntimeEvent::error(
-                                    ErrKind::Value,
-                                    format!(
-                                        "list index {} out of bounds (len = {})",
-                                        i,
-                                        items.len()
-                                    ),
-                                    expr.cursor,
+                                    ErrKind::Type,
+                                    "index list must contain only Num values".into(),
+                                    index.cursor,
                                 ));
                             }
-                            out.push(items[*i].clone());
+                            let i = v.check_index(
+                                items.len(),
+                                index.cursor,
+                                Some("list index".into()),
+                            )?;
+                            out.push(items[i].clone());
                         }
                         Ok(Value::List(Rc::new(RefCell::new(out))))
                     }
@@ -767,53 +878,32 @@ impl<'a> Evaluator<'a> {
                     )),
                 },
                 Value::Str(s) => match index_val {
-                    Value::Num(n) => {
-                        let idx = n.0 as usize;
+                    Value::Num(_) => {
                         let chars: Vec<char> = s.borrow().chars().collect();
-                        if idx >= chars.len() {
-                            return Err(RuntimeEvent::error(
-                                ErrKind::Value,
-                                format!(
-                                    "string index {} out of bounds (len = {})",
-                                    idx,
-                                    chars.len()
-                                ),
-                                expr.cursor,
-                            ));
-                        }
+                        let idx = index_val.check_index(
+                            chars.len(),
+                            index.cursor,
+                            Some("string index".into()),
+                        )?;
                         Ok(Value::Str(Rc::new(RefCell::new(chars[idx].to_string()))))
                     }
                     Value::List(idx_list) => {
-                        let indices: Vec<usize> = idx_list
-                            .borrow()
-                            .iter()
-                            .map(|v| {
-                                if let Value::Num(n) = v {
-                                    Ok(n.0 as usize)
-                                } else {
-                                    Err(RuntimeEvent::error(
-                                        ErrKind::Type,
-                                        "string index list must contain only Num values".into(),
-                                        index.cursor,
-                                    ))
-                                }
-                            })
-                            .collect::<Result<_, _>>()?;
                         let chars: Vec<char> = s.borrow().chars().collect();
                         let mut out = String::new();
-                        for i in indices.iter() {
-                            if *i >= chars.len() {
+                        for v in idx_list.borrow().iter() {
+                            if !matches!(v, Value::Num(_)) {
                                 return Err(RuntimeEvent::error(
-                                    ErrKind::Value,
-                                    format!(
-                                        "string index {} out of bounds (len = {})",
-                                        i,
-                                        chars.len()
-                                    ),
-                                    expr.cursor,
+                                    ErrKind::Type,
+                                    "string index list must contain only Num values".into(),
+                                    index.cursor,
                                 ));
                             }
-                            out.push(chars[*i]);
+                            let i = v.check_index(
+                                chars.len(),
+                                index.cursor,
+                                Some("string index".into()),
+                            )?;
+                            out.push(chars[i]);
                         }
                         Ok(Value::Str(Rc::new(RefCell::new(out))))
                     }
@@ -833,13 +923,75 @@ impl<'a> Evaluator<'a> {
         unreachable!("Non-index passed to eval_expr_index");
     }
 
+    // list[i] op= val and dict[key] op= val, reads the element and stores the result back
+    fn eval_index_compound_assign(
+        &mut self,
+        base_val: Value,
+        index: &Expr,
+        op: &AssignOp,
+        val: &Expr,
+        cursor: Cursor,
+    ) -> EvalResult<Value> {
+        let index_val = self.eval_expr(index)?;
+        let rhs_val = self.eval_expr(val)?;
+
+        match (base_val, index_val) {
+            (_, Value::List(_)) => Err(RuntimeEvent::error(
+                ErrKind::Type,
+                "compound assignment needs a single index, not a List or range".into(),
+                index.cursor,
+            )),
+            (Value::Dict(map), index_val) => {
+                let key = ValueKey::try_from(&index_val).map_err(|_| {
+                    RuntimeEvent::error(
+                        ErrKind::Type,
+                        "dict index must be Null, Bool, Num or Str".into(),
+                        index.cursor,
+                    )
+                })?;
+                let current = map.borrow().get(&key).cloned().unwrap_or(Value::Null);
+                let new_val = current.apply_assign(op, rhs_val, cursor)?;
+                map.borrow_mut().insert(key, new_val.clone());
+                Ok(new_val)
+            }
+            (Value::List(items), index_val @ Value::Num(_)) => {
+                let idx = index_val.check_index(
+                    items.borrow().len(),
+                    index.cursor,
+                    Some("list index".into()),
+                )?;
+                let current = items.borrow()[idx].clone();
+                let new_val = current.apply_assign(op, rhs_val, cursor)?;
+                items.borrow_mut()[idx] = new_val.clone();
+                Ok(new_val)
+            }
+            (Value::List(_), _) => Err(RuntimeEvent::error(
+                ErrKind::Type,
+                "list index must be a Num or List of Nums".into(),
+                index.cursor,
+            )),
+            _ => Err(RuntimeEvent::error(
+                ErrKind::Type,
+                "compound assignment to an index is only supported on Lists and Dicts".into(),
+                cursor,
+            )),
+        }
+    }
+
     fn eval_expr_index_set(&mut self, expr: &Expr) -> EvalResult<Value> {
         if let ExprKind::IndexSet {
-            obj, index, val, ..
+            obj,
+            index,
+            op,
+            val,
         } = &expr.kind
         {
             let base_val = self.eval_expr(obj)?;
 
+            if !matches!(op, AssignOp::Value) {
+                return self.eval_index_compound_assign(base_val, index, op, val, expr.cursor);
+            }
+
             // slice assignment for range index
             if let ExprKind::Range {
                 start,
@@ -942,20 +1094,12 @@ impl<'a> Evaluator<'a> {
                                 })
                             })
                             .collect::<Result<_, _>>()?;
-                        let map_ref = map.borrow();
-                        let mut out = Vec::with_capacity(keys.len());
-                        for key in keys.iter() {
-                            if let Some(v) = map_ref.get(key) {
-                                out.push(v.clone());
-                            } else {
-                                return Err(RuntimeEvent::error(
-                                    ErrKind::Value,
-                                    "dict index not found".into(),
-                                    expr.cursor,
-                                ));
-                            }
+                        let set_val = self.eval_expr(val)?;
+                        let mut map_ref = map.borrow_mut();
+                        for key in keys {
+                            map_ref.insert(key, set_val.clone());
                         }
-                        Ok(Value::List(Rc::new(RefCell::new(out))))
+                        Ok(set_val)
                     }
                     _ => {
                         let key = ValueKey::try_from(&index_val).map_err(|_| {
@@ -965,44 +1109,29 @@ impl<'a> Evaluator<'a> {
                                 index.cursor,
                             )
                         })?;
+                        // assigning to a missing key inserts it
                         let set_val = self.eval_expr(val)?;
-                        if let Some(v) = map.borrow_mut().get_mut(&key) {
-                            *v = set_val.clone();
-                            Ok(set_val)
-                        } else {
-                            Err(RuntimeEvent::error(
-                                ErrKind::Value,
-                                "dict index not found".into(),
-                                expr.cursor,
-                            ))
-                        }
+                        map.borrow_mut().insert(key, set_val.clone());
+                        Ok(set_val)
                     }
                 },
                 Value::List(items) => match index_val {
-                    Value::Num(n) => {
-                        let idx = n.0 as usize;
-                        if idx >= items.borrow().len() {
-                            return Err(RuntimeEvent::error(
-                                ErrKind::Value,
-                                format!(
-                                    "list index {} out of bounds (len = {})",
-                                    idx,
-                                    items.borrow().len()
-                                ),
-                                expr.cursor,
-                            ));
-                        }
+                    Value::Num(_) => {
+                        let len = items.borrow().len();
+                        let idx =
+                            index_val.check_index(len, index.cursor, Some("list index".into()))?;
                         let set_val = self.eval_expr(val)?;
                         items.borrow_mut()[idx] = set_val.clone();
                         Ok(set_val)
                     }
                     Value::List(idx_list) => {
+                        let len = items.borrow().len();
                         let indices: Vec<usize> = idx_list
                             .borrow()
                             .iter()
                             .map(|v| {
-                                if let Value::Num(n) = v {
-                                    Ok(n.0 as usize)
+                                if let Value::Num(_) = v {
+                                    v.check_index(len, index.cursor, Some("list index".into()))
                                 } else {
                                     Err(RuntimeEvent::error(
                                         ErrKind::Type,
@@ -1014,17 +1143,6 @@ impl<'a> Evaluator<'a> {
                             .collect::<Result<_, _>>()?;
                         let set_val = self.eval_expr(val)?;
                         for i in indices.iter() {
-                            if *i >= items.borrow().len() {
-                                return Err(RuntimeEvent::error(
-                                    ErrKind::Value,
-                                    format!(
-                                        "list index {} out of bounds (len = {})",
-                                        i,
-                                        items.borrow().len()
-                                    ),
-                                    expr.cursor,
-                                ));
-                            }
                             items.borrow_mut()[*i] = set_val.clone();
                         }
                         Ok(set_val)
@@ -1036,20 +1154,17 @@ impl<'a> Evaluator<'a> {
                     )),
                 },
                 Value::Str(s) => match index_val {
-                    Value::Num(n) => {
-                        let idx = n.0 as usize;
-                        let len = s.borrow().chars().count();
-                        if idx >= len {
-                            return Err(RuntimeEvent::error(
-                                ErrKind::Value,
-                                format!("string index {} out of bounds (len = {})", idx, len),
-                                expr.cursor,
-                            ));
-                        }
+                    Value::Num(_) => {
+                        let mut buf: Vec<char> = s.borrow().chars().collect();
+                        let idx = index_val.check_index(
+                            buf.len(),
+                            index.cursor,
+                            Some("string index".into()),
+                        )?;
                         let set_val = self.eval_expr(val)?;
                         if let Value::Str(set_str) = set_val.clone() {
-                            s.borrow_mut()
-                                .replace_range(idx..=idx, set_str.borrow().as_str());
+                            buf.splice(idx..=idx, set_str.borrow().chars());
+                            *s.borrow_mut() = buf.into_iter().collect();
                             Ok(set_val)
                         } else {
                             Err(RuntimeEvent::error(
@@ -1060,12 +1175,13 @@ impl<'a> Evaluator<'a> {
                         }
                     }
                     Value::List(idx_list) => {
+                        let len = s.borrow().chars().count();
                         let indices: Vec<usize> = idx_list
                             .borrow()
                             .iter()
                             .map(|v| {
-                                if let Value::Num(n) = v {
-                                    Ok(n.0 as usize)
+                                if let Value::Num(_) = v {
+                                    v.check_index(len, index.cursor, Some("string index".into()))
                                 } else {
                                     Err(RuntimeEvent::error(
                                         ErrKind::Type,
@@ -1087,17 +1203,6 @@ impl<'a> Evaluator<'a> {
                         };
                         let mut buf: Vec<char> = s.borrow().chars().collect();
                         for i in indices.iter() {
-                            if *i >= buf.len() {
-                                return Err(RuntimeEvent::error(
-                                    ErrKind::Value,
-                                    format!(
-                                        "string index {} out of bounds (len = {})",
-                                        i,
-                                        buf.len()
-                                    ),
-                                    expr.cursor,
-                                ));
-                            }
                             if let Some(ch) = repl.chars().next() {
                                 buf[*i] = ch;
                             }
@@ -1130,74 +1235,109 @@ impl<'a> Evaluator<'a> {
                 args_values.push(self.eval_expr(arg)?);
             }
 
-            if let Value::Callable(c) = callee {
-                if args_values.len() != c.arity() {
-                    return Err(RuntimeEvent::error(
-                        ErrKind::Arity,
-                        format!(
-                            "function expects {} arguments but got {}",
-                            c.arity(),
-                            args_values.len()
-                        ),
-                        expr.cursor,
-                    ));
-                }
-                let call_res =
-                    catch_unwind(AssertUnwindSafe(|| c.call(self, args_values, expr.cursor)));
-                let res = match call_res {
-                    Ok(r) => r,
-                    Err(payload) => {
-                        let msg = if let Some(s) = payload.downcast_ref::<&str>() {
-                            s.to_string()
-                        } else if let Some(s) = payload.downcast_ref::<String>() {
-                            s.clone()
-                        } else {
-                            "native panic".to_string()
-                        };
-                        return Err(RuntimeEvent::error(ErrKind::Native, msg, expr.cursor));
-                    }
-                };
-                return Ok(res?);
-            }
+            return self.call_value(callee, args_values, expr.cursor);
+        }
+        unreachable!("Non-call passed to Evaluator::eval_expr_call");
+    }
 
-            if let Value::Obj(obj) = callee {
-                if args_values.len() != obj.arity() {
-                    return Err(RuntimeEvent::error(
-                        ErrKind::Arity,
-                        format!(
-                            "object initializer expects {} arguments but got {}",
-                            obj.arity(),
-                            args_values.len()
-                        ),
-                        expr.cursor,
-                    ));
-                }
-                let call_res = catch_unwind(AssertUnwindSafe(|| {
-                    obj.call(self, args_values, expr.cursor)
-                }));
-                let res = match call_res {
-                    Ok(r) => r,
-                    Err(payload) => {
-                        let msg = if let Some(s) = payload.downcast_ref::<&str>() {
-                            s.to_string()
-                        } else if let Some(s) = payload.downcast_ref::<String>() {
-                            s.clone()
-                        } else {
-                            "native panic".to_string()
-                        };
-                        return Err(RuntimeEvent::error(ErrKind::Native, msg, expr.cursor));
-                    }
+    /// Calls a Callable or an Obj initializer with already evaluated arguments, natives that take
+    /// callbacks (eg. `list.map(fn)`) use this so callbacks behave the same as a call expression
+    pub fn call_value(
+        &mut self,
+        callee: Value,
+        args: Vec<Value>,
+        cursor: Cursor,
+    ) -> EvalResult<Value> {
+        if let Value::Callable(c) = callee {
+            Self::check_arity(c.as_ref(), "function", args.len(), cursor)?;
+            return self.call_catching_panics(c.as_ref(), args, cursor);
+        }
+
+        if let Value::Obj(obj) = callee {
+            Self::check_arity(obj.as_ref(), "object initializer", args.len(), cursor)?;
+            return self.call_catching_panics(obj.as_ref(), args, cursor);
+        }
+
+        Err(RuntimeEvent::error(
+            ErrKind::Type,
+            "can only call functions or objects".into(),
+            cursor,
+        ))
+    }
+
+    /// Call `callee`, a panic in native code becomes a NativeErr instead of taking down the
+    /// interpreter
+    fn call_catching_panics(
+        &mut self,
+        callee: &dyn Callable,
+        args: Vec<Value>,
+        cursor: Cursor,
+    ) -> EvalResult<Value> {
+        catch_unwind(AssertUnwindSafe(|| callee.call(self, args, cursor))).unwrap_or_else(
+            |payload| {
+                let msg = if let Some(s) = payload.downcast_ref::<&str>() {
+                    s.to_string()
+                } else if let Some(s) = payload.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "native panic".to_string()
                 };
-                return Ok(res?);
-            }
+                Err(RuntimeEvent::error(ErrKind::Native, msg, cursor))
+            },
+        )
+    }
 
-            return Err(RuntimeEvent::error(
-                ErrKind::Type,
-                "can only call functions or objects".into(),
+    /// ArityErr if `callee` doesn't take `count` arguments, `what` names the kind of callee in
+    /// the message
+    fn check_arity(
+        callee: &dyn Callable,
+        what: &str,
+        count: usize,
+        cursor: Cursor,
+    ) -> EvalResult<()> {
+        if count >= callee.min_arity() && count <= callee.arity() {
+            return Ok(());
+        }
+        let expected = if callee.min_arity() == callee.arity() {
+            callee.arity().to_string()
+        } else if callee.arity() == usize::MAX {
+            format!("at least {}", callee.min_arity())
+        } else {
+            format!("{} to {}", callee.min_arity(), callee.arity())
+        };
+        Err(RuntimeEvent::error(
+            ErrKind::Arity,
+            format!("{} expects {} arguments but got {}", what, expected, count),
+            cursor,
+        ))
+    }
+
+    fn eval_expr_lambda(&mut self, expr: &Expr) -> EvalResult<Value> {
+        if let ExprKind::Lambda {
+            params,
+            defaults,
+            body,
+        } = &expr.kind
+        {
+            // lambdas are anonymous functions, so they're evaluated as an unnamed fn declaration
+            // that captures the current environment
+            let declr = Stmt::new(
+                StmtKind::Fn {
+                    name: "lambda".into(),
+                    params: params.clone(),
+                    defaults: defaults.clone(),
+                    body: body.clone(),
+                    bound: false,
+                },
                 expr.cursor,
-            ));
+            );
+            return Ok(Value::Callable(Rc::new(Function::new(
+                declr,
+                self.env.clone(),
+                false,
+            ))));
         }
-        unreachable!("Non-call passed to Evaluator::eval_expr_call");
+        unreachable!("Non-lambda passed to Evaluator::eval_expr_lambda");
     }
 
     fn eval_expr_get(&mut self, expr: &Expr) -> EvalResult<Value> {
@@ -1209,8 +1349,11 @@ impl<'a> Evaluator<'a> {
                 return Ok(Instance::get_rc(inst.clone(), name.clone(), expr.cursor)?);
             }
 
-            // static methods
+            // static constants and methods
             if let Value::Obj(obj) = val {
+                if let Some(val) = obj.consts.get(name) {
+                    return Ok(val.clone());
+                }
                 if let Some(method) = obj.methods.get(&name.clone()) {
                     if !method.get_bound() {
                         return Ok(Value::Callable(method.get_callable()));
@@ -1265,14 +1408,10 @@ impl<'a> Evaluator<'a> {
                 let rhs_val = self.eval_expr(val)?;
 
                 let new_val = match op {
-                    AssignOp::Value => rhs_val.clone(),
-                    AssignOp::Add => {
-                        let current = Instance::get_rc(inst.clone(), name.clone(), expr.cursor)?;
-                        current.add_assign(rhs_val, expr.cursor)?
-                    }
-                    AssignOp::Sub => {
+                    AssignOp::Value => rhs_val,
+                    _ => {
                         let current = Instance::get_rc(inst.clone(), name.clone(), expr.cursor)?;
-                        current.sub_assign(rhs_val, expr.cursor)?
+                        current.apply_assign(op, rhs_val, expr.cursor)?
                     }
                 };
 
@@ -1316,19 +1455,30 @@ impl<'a> Evaluator<'a> {
             let cursor = expr.cursor;
 
             return match op {
-                BinaryOp::Add => {
-                    if let (Value::Num(ln), Value::Num(rn)) = (left.clone(), right.clone()) {
-                        Ok(Value::Num(ln + rn))
-                    } else if let (Value::Str(ls), Value::Str(rs)) = (left, right) {
-                        Ok(Value::Str(Rc::new(RefCell::new(format!(
-                            "{}{}",
-                            ls.borrow(),
-                            rs.borrow()
-                        )))))
-                    } else {
-                        Ok(Value::Null)
+                BinaryOp::Add => match (&left, &right) {
+                    (Value::Num(ln), Value::Num(rn)) => Ok(Value::Num(ln + rn)),
+                    (Value::Str(ls), Value::Str(rs)) => Ok(Value::Str(Rc::new(RefCell::new(
+                        format!("{}{}", ls.borrow(), rs.borrow()),
+                    )))),
+                    _ => {
+                        let note = match (&left, &right) {
+                            (Value::Str(_), _) | (_, Value::Str(_)) => {
+                                "use to_str() or parse_num() to convert one side first"
+                            }
+                            _ => "only Num + Num and Str + Str are supported",
+                        };
+                        Err(RuntimeEvent::error_with_note(
+                            ErrKind::Type,
+                            format!(
+                                "cannot add values of type {} and {}",
+                                left.get_type(),
+                                right.get_type()
+                            ),
+                            note.into(),
+                            cursor,
+                        ))
                     }
-                }
+                },
                 BinaryOp::Sub => Ok(Value::Num(OrderedFloat(
                     left.check_num(cursor, None)? - right.check_num(cursor, None)?,
                 ))),
@@ -1338,6 +1488,18 @@ impl<'a> Evaluator<'a> {
                 BinaryOp::Div => Ok(Value::Num(OrderedFloat(
                     left.check_num(cursor, None)? / right.check_num(cursor, None)?,
                 ))),
+                BinaryOp::FloorDiv => {
+                    let l = left.check_num(cursor, None)?;
+                    let r = right.check_num(cursor, None)?;
+                    if r == 0. {
+                        return Err(RuntimeEvent::error(
+                            ErrKind::Value,
+                            "division by zero".into(),
+                            cursor,
+                        ));
+                    }
+                    Ok(Value::Num(OrderedFloat((l / r).floor())))
+                }
                 BinaryOp::Mod => Ok(Value::Num(OrderedFloat(
                     left.check_num(cursor, None)? % right.check_num(cursor, None)?,
                 ))),
@@ -1345,20 +1507,51 @@ impl<'a> Evaluator<'a> {
                     left.check_num(cursor, None)?
                         .powf(right.check_num(cursor, None)?),
                 ))),
+                BinaryOp::BitAnd => {
+                    let (l, r) = Self::bitwise_operands(&left, &right, cursor)?;
+                    Ok(Value::Num(OrderedFloat((l & r) as f64)))
+                }
+                BinaryOp::BitOr => {
+                    let (l, r) = Self::bitwise_operands(&left, &right, cursor)?;
+                    Ok(Value::Num(OrderedFloat((l | r) as f64)))
+                }
+                BinaryOp::BitXor => {
+                    let (l, r) = Self::bitwise_operands(&left, &right, cursor)?;
+                    Ok(Value::Num(OrderedFloat((l ^ r) as f64)))
+                }
+                BinaryOp::ShiftLeft | BinaryOp::ShiftRight => {
+                    let (l, r) = Self::bitwise_operands(&left, &right, cursor)?;
+                    let shifted = u32::try_from(r).ok().and_then(|r| match op {
+                        BinaryOp::ShiftLeft => l.checked_shl(r),
+                        _ => l.checked_shr(r),
+                    });
+                    match shifted {
+                        Some(n) => Ok(Value::Num(OrderedFloat(n as f64))),
+                        None => Err(RuntimeEvent::error(
+                            ErrKind::Value,
+                            format!("shift amount must be between 0 and 63, found {}", r),
+                            cursor,
+                        )),
+                    }
+                }
                 BinaryOp::Equals => Ok(Value::Bool(left.is_equal(&right))),
                 BinaryOp::NotEquals => Ok(Value::Bool(!left.is_equal(&right))),
-                BinaryOp::Greater => Ok(Value::Bool(
-                    left.check_num(cursor, None)? > right.check_num(cursor, None)?,
-                )),
-                BinaryOp::GreaterEquals => Ok(Value::Bool(
-                    left.check_num(cursor, None)? >= right.check_num(cursor, None)?,
-                )),
-                BinaryOp::Lesser => Ok(Value::Bool(
-                    left.check_num(cursor, None)? < right.check_num(cursor, None)?,
-                )),
-                BinaryOp::LesserEquals => Ok(Value::Bool(
-                    left.check_num(cursor, None)? <= right.check_num(cursor, None)?,
-                )),
+                BinaryOp::Greater => Ok(Value::Bool(matches!(
+                    left.compare(&right, cursor)?,
+                    Some(Ordering::Greater)
+                ))),
+                BinaryOp::GreaterEquals => Ok(Value::Bool(matches!(
+                    left.compare(&right, cursor)?,
+                    Some(Ordering::Greater | Ordering::Equal)
+                ))),
+                BinaryOp::Lesser => Ok(Value::Bool(matches!(
+                    left.compare(&right, cursor)?,
+                    Some(Ordering::Less)
+                ))),
+                BinaryOp::LesserEquals => Ok(Value::Bool(matches!(
+                    left.compare(&right, cursor)?,
+                    Some(Ordering::Less | Ordering::Equal)
+                ))),
                 BinaryOp::Nullish => {
                     if let Value::Null = left {
                         Ok(right)
@@ -1371,6 +1564,24 @@ impl<'a> Evaluator<'a> {
         unreachable!("Non-binary passed to Evaluator::eval_expr_binary");
     }
 
+    /// Bitwise operators work on whole Nums as 64 bit integers
+    fn bitwise_operands(left: &Value, right: &Value, cursor: Cursor) -> EvalResult<(i64, i64)> {
+        let mut ints = [0; 2];
+        for (i, val) in [left, right].into_iter().enumerate() {
+            let n = val.check_num(cursor, Some("bitwise operand".into()))?;
+            // i64::MAX as f64 rounds up to 2^63, which is already out of range
+            if n.fract() != 0.0 || !(i64::MIN as f64..i64::MAX as f64).contains(&n) {
+                return Err(RuntimeEvent::error(
+                    ErrKind::Value,
+                    format!("bitwise operands must be 64 bit integers, found {}", val),
+                    cursor,
+                ));
+            }
+            ints[i] = n as i64;
+        }
+        Ok((ints[0], ints[1]))
+    }
+
     // Utility functions
 
     pub fn lookup_var(&self, name: &str, expr: &Expr) -> EvalResult<Value> {
@@ -1381,3 +1592,1035 @@ impl<'a> Evaluator<'a> {
         }
     }
 }
+
+// Unit tests
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{
+        evaluator::resolver::Resolver,
+        lexer::Lexer,
+        lexer::cursor::Cursor,
+        native_fn,
+        parser::{Parser, tests::parse},
+    };
+
+    /// Lex, parse and resolve `text`
+    pub(crate) fn resolved_src(text: &str) -> Src {
+        let mut src = Src::from_text(PathBuf::from("test.qte"), text.to_string());
+        src.tokens = Lexer::new(src.text.clone()).tokenize().tokens;
+        assert!(src.tokens.is_some(), "lexer failed");
+        src.ast = Parser::new(&src).parse().ast;
+        assert!(src.ast.is_some(), "parser failed");
+        src.ast = Resolver::new(&src).resolve().ast;
+        assert!(src.ast.is_some(), "resolver failed");
+        src
+    }
+
+    /// A program with no statements, for tests that call natives directly
+    pub(crate) fn empty_src() -> Src {
+        let mut src = Src::from_text(PathBuf::from("test.qte"), String::new());
+        src.ast = Some(Vec::new());
+        src
+    }
+
+    /// Run `text` through the whole pipeline and return the eval result and globals
+    pub(crate) fn run(text: &str) -> (EvalResult<()>, EnvPtr) {
+        run_with_args(text, Vec::new())
+    }
+
+    /// Same as `run`, passing `args` as the program's command line arguments
+    pub(crate) fn run_with_args(text: &str, args: Vec<String>) -> (EvalResult<()>, EnvPtr) {
+        let src = resolved_src(text);
+        let mut evaluator = Evaluator::with_args(&src, args);
+        let result = evaluator.eval();
+        (result, evaluator.globals.clone())
+    }
+
+    /// Run `text` and return the value of the global `name`
+    pub(crate) fn global(text: &str, name: &str) -> Value {
+        let (result, globals) = run(text);
+        assert!(result.is_ok(), "eval failed: {:?}", result.err());
+        globals
+            .borrow()
+            .get(name, Cursor::new())
+            .expect("undefined global")
+    }
+
+    /// Evaluate the expression `expr` and return its value as it would be printed
+    pub(crate) fn eval(expr: &str) -> String {
+        global(format!("var x = {}", expr).as_str(), "x").to_string()
+    }
+
+    /// Assert that `result` is a runtime error of the given `kind`
+    #[track_caller]
+    pub(crate) fn assert_err_kind<T: std::fmt::Debug>(result: EvalResult<T>, kind: ErrKind) {
+        match result {
+            Err(RuntimeEvent::Err(err)) => assert_eq!(err.kind, kind, "{}", err.msg),
+            other => panic!("expected a {}, got {:?}", kind.to_string(), other),
+        }
+    }
+
+    #[test]
+    fn list_literals() {
+        assert_eq!(global("var x = [1, 2, 3]", "x").to_string(), "[1, 2, 3]");
+        assert_eq!(global("var x = []", "x").to_string(), "[]");
+        assert_eq!(global("var x = [1, 2,]", "x").to_string(), "[1, 2]");
+        assert_eq!(
+            global("var x = [\n  \"a\",\n  \"b\",\n]", "x").to_string(),
+            "[\"a\", \"b\"]"
+        );
+    }
+
+    #[test]
+    fn dict_literals() {
+        assert_eq!(global("var x = {}.len()", "x").to_string(), "0");
+        assert_eq!(
+            global("var x = {\"a\": 1, \"b\": 2,}.len()", "x").to_string(),
+            "2"
+        );
+    }
+
+    #[test]
+    fn dict_index() {
+        let src = "
+var d = {\"b\": 1, \"a\": 2, 3: true}
+var read = d[\"a\"]
+var num_key = d[3]
+var missing = d[\"nope\"]
+var many = d[[\"b\", \"nope\"]]
+d[\"a\"] = 20
+d[\"c\"] = 30
+var keys = d.keys()
+var values = d.values()
+";
+        assert_eq!(global(src, "read").to_string(), "2");
+        assert_eq!(global(src, "num_key").to_string(), "true");
+        assert_eq!(global(src, "missing").to_string(), "null");
+        assert_eq!(global(src, "many").to_string(), "[1, null]");
+        // insertion order is kept, overwriting a key keeps its position
+        assert_eq!(global(src, "keys").to_string(), "[\"b\", \"a\", 3, \"c\"]");
+        assert_eq!(global(src, "values").to_string(), "[1, 20, true, 30]");
+
+        for src in [
+            "var x = {[1]: 2}",
+            "var x = {}[[[1]]]",
+            "var x = {}\nx[{}] = 1",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn logical_short_circuit() {
+        let src = "
+var calls = 0
+fn bump() do
+    calls += 1
+    return true
+end
+var a = false and bump()
+var b = true or bump()
+var c = true and bump()
+var d = false or bump()
+";
+        assert_eq!(global(src, "calls").to_string(), "2");
+        assert_eq!(global(src, "a").to_string(), "false");
+        assert_eq!(global(src, "b").to_string(), "true");
+        assert_eq!(global(src, "c").to_string(), "true");
+        assert_eq!(global(src, "d").to_string(), "true");
+    }
+
+    #[test]
+    fn logical_not() {
+        assert_eq!(global("var x = not true", "x").to_string(), "false");
+        assert_eq!(global("var x = not 0", "x").to_string(), "true");
+        assert_eq!(
+            global("var x = not false and true", "x").to_string(),
+            "true"
+        );
+    }
+
+    #[test]
+    fn unary_ops() {
+        assert_eq!(global("var x = -5", "x").to_string(), "-5");
+        assert_eq!(global("var x = !true", "x").to_string(), "false");
+        assert_eq!(global("var x = !!0", "x").to_string(), "false");
+        assert_eq!(global("var x = 3 - -2", "x").to_string(), "5");
+        assert_eq!(global("var x = - -2", "x").to_string(), "2");
+        assert_eq!(global("var y = 4\nvar x = -y * 2", "x").to_string(), "-8");
+        assert_eq!(global("var x = -2 + 3", "x").to_string(), "1");
+
+        let (result, _) = run("var x = -\"a\"");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn power_op() {
+        assert_eq!(global("var x = 2 ** 10", "x").to_string(), "1024");
+        assert_eq!(global("var x = 2 ** 3 ** 2", "x").to_string(), "512");
+        assert_eq!(global("var x = -2 ** 2", "x").to_string(), "-4");
+        assert_eq!(global("var x = 2 ** -1", "x").to_string(), "0.5");
+        assert_eq!(global("var x = 3 * 2 ** 2", "x").to_string(), "12");
+        assert_eq!(global("var x = 0 ** 0", "x").to_string(), "1");
+
+        let (result, _) = run("var x = \"a\" ** 2");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        assert_eq!(global("var x = 6 & 3", "x").to_string(), "2");
+        assert_eq!(global("var x = 6 | 3", "x").to_string(), "7");
+        assert_eq!(global("var x = 6 ^ 3", "x").to_string(), "5");
+        assert_eq!(global("var x = 1 << 4", "x").to_string(), "16");
+        assert_eq!(global("var x = -16 >> 2", "x").to_string(), "-4");
+        // binds looser than arithmetic and tighter than comparison
+        assert_eq!(global("var x = 1 + 1 << 2", "x").to_string(), "8");
+        assert_eq!(global("var x = 5 & 1 == 1", "x").to_string(), "true");
+        assert_eq!(global("var x = 1 | 6 & 3", "x").to_string(), "3");
+
+        assert_eq!(
+            global("var x = -(2 ** 63) | 0", "x").to_string(),
+            "-9223372036854775808"
+        );
+        for src in [
+            "var x = 1.5 & 1",
+            "var x = 1 << 64",
+            "var x = 1 >> -1",
+            "var x = 2 ** 70 | 0",
+            "var x = 2 ** 63 | 0",
+            "var x = (1 / 0) & 1",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+        let (result, _) = run("var x = \"a\" & 1");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn floor_div_op() {
+        assert_eq!(global("var x = 7 // 2", "x").to_string(), "3");
+        assert_eq!(global("var x = -7 // 2", "x").to_string(), "-4");
+        assert_eq!(global("var x = 7.5 // 0.5", "x").to_string(), "15");
+        // same precedence as `/`, left associative
+        assert_eq!(global("var x = 20 // 3 // 2", "x").to_string(), "3");
+        assert_eq!(global("var x = 1 + 7 // 2 * 2", "x").to_string(), "7");
+        assert_eq!(global("var x = 8 / 2 // 3", "x").to_string(), "1");
+
+        let (result, _) = run("var x = 1 // 0");
+        assert_err_kind(result, ErrKind::Value);
+    }
+
+    #[test]
+    fn compound_assign_ops() {
+        let src = "
+var x = 4
+x *= 3
+var mult = x
+x /= 2
+var div = x
+x %= 4
+var rem = x
+var list = [1, 2]
+list[1] *= 10
+var d = {\"a\": 9}
+d[\"a\"] /= 3
+var key = d[\"a\"]
+obj Box do
+    init() do
+        self.n = 5
+    end
+end
+var b = Box()
+b.n %= 3
+var field = b.n
+";
+        assert_eq!(global(src, "mult").to_string(), "12");
+        assert_eq!(global(src, "div").to_string(), "6");
+        assert_eq!(global(src, "rem").to_string(), "2");
+        assert_eq!(global(src, "list").to_string(), "[1, 20]");
+        assert_eq!(global(src, "key").to_string(), "3");
+        assert_eq!(global(src, "field").to_string(), "2");
+
+        for src in [
+            "var x = \"a\"\nx *= 2",
+            "var x = 2\nx /= \"a\"",
+            "var l = [1]\nl[[0]] *= 2",
+            "var s = \"ab\"\ns[0] *= 2",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+
+        // only variables, fields and indices can be assigned to
+        assert!(parse("1 *= 2").ast.is_none());
+    }
+
+    #[test]
+    fn add_assign_str_and_list() {
+        let src = "
+var s = \"ab\"
+s += \"x\"
+s += 1
+var list = [1, 2, 3]
+list += [4]
+list += 5
+list += [[6]]
+var twice = [1, 2]
+twice += twice
+";
+        assert_eq!(global(src, "s").to_string(), "abx1");
+        assert_eq!(global(src, "list").to_string(), "[1, 2, 3, 4, 5, [6]]");
+        assert_eq!(global(src, "twice").to_string(), "[1, 2, 1, 2]");
+
+        for (src, msg) in [
+            (
+                "var s = \"ab\"\ns -= \"b\"",
+                "cannot use '-=' on a value of type Str",
+            ),
+            (
+                "var l = [1]\nl -= [1]",
+                "cannot use '-=' on a value of type List",
+            ),
+            (
+                "var n = 1\nn += \"a\"",
+                "cannot use '+=' with values of type Num and Str",
+            ),
+            (
+                "var b = true\nb += 1",
+                "cannot use '+=' on a value of type Bool",
+            ),
+        ] {
+            let (result, _) = run(src);
+            match result {
+                Err(RuntimeEvent::Err(RuntimeErr {
+                    kind: ErrKind::Type,
+                    msg: err_msg,
+                    ..
+                })) => assert_eq!(err_msg, msg),
+                _ => panic!("expected a TypeErr for {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn for_in_loops() {
+        let src = "
+var sum = 0
+for n in [1, 2, 3] do
+    sum += n
+end
+var chars = []
+var indices = []
+for ch, i in \"abc\" do
+    chars.push(ch)
+    indices.push(i)
+end
+var list = [1, 2, 3]
+var seen = []
+for n in list do
+    seen.push(n)
+    list.push(n)
+    list.remove(0)
+end
+";
+        assert_eq!(global(src, "sum").to_string(), "6");
+        assert_eq!(global(src, "chars").to_string(), "[\"a\", \"b\", \"c\"]");
+        assert_eq!(global(src, "indices").to_string(), "[0, 1, 2]");
+        // the loop runs over the list as it was when the loop started
+        assert_eq!(global(src, "seen").to_string(), "[1, 2, 3]");
+        assert_eq!(global(src, "list").to_string(), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn if_else_chains() {
+        let src = "
+fn classify(a) do
+    if a < 5 do
+        return \"small\"
+    else if a > 5 do
+        return \"big\"
+    else do
+        return \"five\"
+    end
+end
+var small = classify(1)
+var big = classify(10)
+var five = classify(5)
+";
+        assert_eq!(global(src, "small").to_string(), "small");
+        assert_eq!(global(src, "big").to_string(), "big");
+        assert_eq!(global(src, "five").to_string(), "five");
+    }
+
+    #[test]
+    fn early_return_from_nested_blocks() {
+        let src = "
+fn find(list, target) do
+    var i = 0
+    while i < len(list) do
+        if list[i] == target do
+            return i
+        end
+        i += 1
+    end
+    return -1
+end
+fn first_positive_even(list) do
+    for n in list do
+        if not (n % 2 == 0) do
+            continue
+        else do
+            if n > 0 do
+                return n
+            end
+        end
+    end
+    return Null
+end
+fn guard(x) do
+    if not x do
+        return \"none\"
+    end
+    return \"some\"
+end
+var found = find([5, 6, 7], 6)
+var missing = find([1], 9)
+var even = first_positive_even([1, 3, -2, 4, 6])
+var guarded = guard(false)
+var passed = guard(1)
+";
+        assert_eq!(global(src, "found").to_string(), "1");
+        assert_eq!(global(src, "missing").to_string(), "-1");
+        assert_eq!(global(src, "even").to_string(), "4");
+        assert_eq!(global(src, "guarded").to_string(), "none");
+        assert_eq!(global(src, "passed").to_string(), "some");
+    }
+
+    #[test]
+    fn nested_if_else() {
+        // an else on its own line after an inner `end` belongs to the outer if
+        let src = "
+var x = 0
+if false do
+    if true do
+        x = 1
+    end
+else do
+    x = 2
+end
+";
+        assert_eq!(global(src, "x").to_string(), "2");
+
+        // an else directly after an inner `end` binds to the nearest if
+        let src = "
+var x = 0
+if true do
+    if false do
+        x = 1
+    end else do
+        x = 3
+    end
+end
+";
+        assert_eq!(global(src, "x").to_string(), "3");
+    }
+
+    #[test]
+    fn string_concat_and_compare() {
+        assert_eq!(
+            global("var x = \"foo\" + \"bar\"", "x").to_string(),
+            "foobar"
+        );
+        assert_eq!(global("var x = \"a\" < \"b\"", "x").to_string(), "true");
+        assert_eq!(global("var x = \"b\" <= \"a\"", "x").to_string(), "false");
+        assert_eq!(global("var x = \"abc\" > \"abb\"", "x").to_string(), "true");
+        assert_eq!(global("var x = \"a\" >= \"a\"", "x").to_string(), "true");
+    }
+
+    #[test]
+    fn bool_compare() {
+        assert_eq!(global("var x = false < true", "x").to_string(), "true");
+        assert_eq!(global("var x = true <= true", "x").to_string(), "true");
+        assert_eq!(global("var x = false > true", "x").to_string(), "false");
+        assert_eq!(
+            global("var x = [true, false, true]\nx.sort()", "x").to_string(),
+            "[false, true, true]"
+        );
+
+        for (src, msg) in [
+            ("1 < \"a\"", "cannot compare values of type Num and Str"),
+            ("true > 0", "cannot compare values of type Bool and Num"),
+            (
+                "Null <= false",
+                "cannot compare values of type Null and Bool",
+            ),
+        ] {
+            let (result, _) = run(src);
+            match result {
+                Err(RuntimeEvent::Err(RuntimeErr {
+                    kind: ErrKind::Type,
+                    msg: err_msg,
+                    ..
+                })) => assert_eq!(err_msg, msg),
+                _ => panic!("expected a TypeErr for {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn mixed_type_add_is_type_err() {
+        let (result, _) = run("var x = \"a\" + 1");
+        match result {
+            Err(RuntimeEvent::Err(err)) => {
+                assert!(matches!(err.kind, ErrKind::Type));
+                assert!(err.note.is_some());
+            }
+            _ => panic!("expected a TypeErr"),
+        }
+
+        let (result, _) = run("var x = \"a\" < 1");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn len_counts_chars() {
+        assert_eq!(global("var x = len(\"héllo\")", "x").to_string(), "5");
+        assert_eq!(global("var x = \"héllo\".len()", "x").to_string(), "5");
+        assert_eq!(global("var x = len([1, 2, 3])", "x").to_string(), "3");
+        assert_eq!(global("var x = len({\"a\": 1})", "x").to_string(), "1");
+
+        let (result, _) = run("var x = len(10)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn num_str_conversions() {
+        assert_eq!(global("var x = to_num(\"42\")", "x").to_string(), "42");
+        assert_eq!(global("var x = to_num(\"3.14\")", "x").to_string(), "3.14");
+        assert_eq!(global("var x = to_num(\" 7 \") + 1", "x").to_string(), "8");
+        assert_eq!(global("var x = to_num(5)", "x").to_string(), "5");
+        assert_eq!(global("var x = to_str(10) + \"!\"", "x").to_string(), "10!");
+        assert_eq!(
+            global("var x = to_str([1, \"a\"])", "x").to_string(),
+            "[1, \"a\"]"
+        );
+
+        let (result, _) = run("var x = to_num(\"abc\")");
+        assert_err_kind(result, ErrKind::Value);
+    }
+
+    #[test]
+    fn type_builtin() {
+        let src = "
+obj Pos do
+end
+fn f() do
+end
+var types = [type(Null), type(true), type(1), type(\"a\"), type([]), type({}), type(f), type(Math), type(Pos), type(Pos())]
+";
+        assert_eq!(
+            global(src, "types").to_string(),
+            "[\"Null\", \"Bool\", \"Num\", \"Str\", \"List\", \"Dict\", \"Fn\", \"Math\", \"Pos\", \"Pos\"]"
+        );
+    }
+
+    #[test]
+    fn range_builtin() {
+        assert_eq!(
+            global("var x = range(0, 5)", "x").to_string(),
+            "[0, 1, 2, 3, 4]"
+        );
+        assert_eq!(
+            global("var x = range(0, 6, 2)", "x").to_string(),
+            "[0, 2, 4]"
+        );
+        assert_eq!(
+            global("var x = range(3, 0, -1)", "x").to_string(),
+            "[3, 2, 1]"
+        );
+        assert_eq!(global("var x = range(5, 0)", "x").to_string(), "[]");
+
+        let (result, _) = run("var x = range(0, 5, 0)");
+        assert_err_kind(result, ErrKind::Value);
+        let (result, _) = run("var x = range(0)");
+        assert_err_kind(result, ErrKind::Arity);
+    }
+
+    #[test]
+    fn format_builtin() {
+        assert_eq!(
+            global("var x = format(\"{} + {} = {}\", 1, 2, 3)", "x").to_string(),
+            "1 + 2 = 3"
+        );
+        assert_eq!(
+            global("var x = format(\"{{{}}} {}\", \"a\", [1])", "x").to_string(),
+            "{a} [1]"
+        );
+        assert_eq!(global("var x = format(\"}}\")", "x").to_string(), "}");
+
+        for src in [
+            "format(\"{} {}\", 1)",
+            "format(\"{}\", 1, 2)",
+            "format(\"{ }\", 1)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+
+    #[test]
+    fn assert_builtins() {
+        assert_eq!(global("var x = assert(1 < 2)", "x").to_string(), "null");
+        assert_eq!(
+            global("var x = assert_eq([1, \"a\"], [1, \"a\"])", "x").to_string(),
+            "null"
+        );
+
+        for (src, expected) in [
+            ("assert(1 > 2)", "assertion failed"),
+            ("assert(Null, \"no value\")", "assertion failed: no value"),
+            ("assert_eq(1 + 1, 3)", "assertion failed: expected 3, got 2"),
+            (
+                "assert_eq(\"1\", 1)",
+                "assertion failed: expected 1, got \"1\"",
+            ),
+        ] {
+            let (result, _) = run(src);
+            match result {
+                Err(RuntimeEvent::Err(err)) => {
+                    assert!(matches!(err.kind, ErrKind::Value));
+                    assert_eq!(err.msg, expected);
+                    assert!(err.note.is_some());
+                }
+                _ => panic!("expected a ValueErr from {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn lambdas() {
+        let src = "
+var inc = fn(x) = x + 1
+var a = inc(1)
+var base = 10
+var add_base = fn(x) do
+    return x + base
+end
+base = 20
+var b = add_base(1)
+fn make_counter() do
+    var count = 0
+    return fn() do
+        count += 1
+        return count
+    end
+end
+var counter = make_counter()
+counter()
+var c = counter()
+var d = (fn(a, b) = a * b)(3, 4)
+";
+        assert_eq!(global(src, "a").to_string(), "2");
+        assert_eq!(global(src, "b").to_string(), "21");
+        assert_eq!(global(src, "c").to_string(), "2");
+        assert_eq!(global(src, "d").to_string(), "12");
+        assert_eq!(
+            global("var f = fn() = 1\nvar t = type(f)", "t").to_string(),
+            "Fn"
+        );
+    }
+
+    #[test]
+    fn default_params() {
+        let src = "
+var greeting = \"hello\"
+fn greet(name, greeting = greeting + \",\") do
+    return greeting + \" \" + name
+end
+greeting = \"hi\"
+var a = greet(\"bob\")
+var b = greet(\"bob\", \"hey\")
+var scale = fn(x, by = 2) = x * by
+var c = scale(3)
+var d = scale(3, 3)
+obj Point do
+    init(x = 0, y = 1) do
+        self.x = x
+        self.y = y
+    end
+end
+var p = Point(4)
+var e = p.x + p.y
+var f = Point().x
+";
+        assert_eq!(global(src, "a").to_string(), "hi, bob");
+        assert_eq!(global(src, "b").to_string(), "hey bob");
+        assert_eq!(global(src, "c").to_string(), "6");
+        assert_eq!(global(src, "d").to_string(), "9");
+        assert_eq!(global(src, "e").to_string(), "5");
+        assert_eq!(global(src, "f").to_string(), "0");
+
+        for src in [
+            "fn f(a, b = 1) do end\nf(1, 2, 3)",
+            "fn f(a, b = 1) do end\nf()",
+            "obj O do\ninit(a = 1) do end\nend\nO(1, 2)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Arity);
+        }
+
+        // defaults are evaluated outside the function, so other parameters aren't visible
+        let (result, _) = run("fn f(a, b = a) do end\nf(1)");
+        assert_err_kind(result, ErrKind::Name);
+    }
+
+    #[test]
+    fn string_interpolation() {
+        let src = "
+var x = 5
+var a = \"val=${x}\"
+var b = \"\\$5\"
+var c = \"${x} + ${x * 2} = ${x + x * 2}!\"
+var d = \"list: ${[1, \"a\"]}, nested: ${\"(${x})\"}\"
+";
+        assert_eq!(global(src, "a").to_string(), "val=5");
+        assert_eq!(global(src, "b").to_string(), "$5");
+        assert_eq!(global(src, "c").to_string(), "5 + 10 = 15!");
+        assert_eq!(
+            global(src, "d").to_string(),
+            "list: [1, \"a\"], nested: (5)"
+        );
+    }
+
+    // fails with an io::Error, which carries no location of its own
+    native_fn!(FnFailIo, "fail_io", 0, |_evaluator, _args, _cursor| {
+        std::fs::read_to_string("/nonexistent/quetite/file")?;
+        Ok(Value::Null)
+    });
+
+    #[test]
+    fn native_errors_point_at_call_site() {
+        let src = resolved_src("var a = 1\nvar b = Math.sin(\"x\")\n");
+        let err = Evaluator::new(&src).eval().unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeEvent::Err(RuntimeErr {
+                kind: ErrKind::Type,
+                cursor: Cursor { line: 1, .. },
+                ..
+            })
+        ));
+
+        let src = resolved_src("var a = 1\n\nfail_io()\n");
+        let mut evaluator = Evaluator::new(&src);
+        evaluator
+            .globals
+            .borrow_mut()
+            .define("fail_io".into(), Value::Callable(Rc::new(FnFailIo)));
+        assert!(matches!(
+            evaluator.eval().unwrap_err(),
+            RuntimeEvent::Err(RuntimeErr {
+                kind: ErrKind::IO,
+                cursor: Cursor { line: 2, .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn chained_method_calls() {
+        let src = "
+obj Builder do
+    init(self) do
+        self.parts = []
+    end
+    add(self, part) do
+        self.parts.push(part)
+        return self
+    end
+end
+var b = Builder().add(\"a\").add(\"b\").add(\"c\")
+var parts = b.parts
+var upper = \" hi \".trim().upper()
+";
+        assert_eq!(global(src, "parts").to_string(), "[\"a\", \"b\", \"c\"]");
+        assert_eq!(global(src, "upper").to_string(), "HI");
+    }
+
+    #[test]
+    fn trace_globals() {
+        let src =
+            resolved_src("var b = \"hi\"\nvar a = 1\nfn f() do\nend\na = a + 1\nvar len = 3\n");
+        let mut evaluator = Evaluator::new(&src);
+        let natives = evaluator.native_values();
+        assert!(evaluator.eval().is_ok());
+        // globals shadowing a native are the program's own
+        assert_eq!(
+            evaluator.trace_lines(&natives),
+            vec!["a = 2", "b = \"hi\"", "f = <fn f>", "len = 3"]
+        );
+    }
+
+    #[test]
+    fn break_and_continue() {
+        let src = "
+var i = 0
+while true do
+    i += 1
+    if i == 5 do
+        break
+    end
+end
+var evens = []
+for n in 0..10 do
+    if n % 2 != 0 do
+        continue
+    end
+    evens.push(n)
+end
+";
+        assert_eq!(global(src, "i").to_string(), "5");
+        assert_eq!(global(src, "evens").to_string(), "[0, 2, 4, 6, 8]");
+    }
+
+    #[test]
+    fn index_read() {
+        let src = "
+var list = [10, 20, 30]
+var first = list[0]
+var picked = list[[2, 0]]
+var str = \"héllo\"
+var ch = str[1]
+var chs = str[[0, 4]]
+";
+        assert_eq!(global(src, "first").to_string(), "10");
+        assert_eq!(global(src, "picked").to_string(), "[30, 10]");
+        assert_eq!(global(src, "ch").to_string(), "é");
+        assert_eq!(global(src, "chs").to_string(), "ho");
+    }
+
+    #[test]
+    fn index_write() {
+        let src = "
+var list = [1, 2, 3]
+var alias = list
+list[2] = 5
+var str = \"héllo\"
+str[1] = \"e\"
+";
+        assert_eq!(global(src, "list").to_string(), "[1, 2, 5]");
+        assert_eq!(global(src, "alias").to_string(), "[1, 2, 5]");
+        assert_eq!(global(src, "str").to_string(), "hello");
+    }
+
+    #[test]
+    fn index_out_of_bounds() {
+        for src in [
+            "var x = [1, 2][2]",
+            "var x = [1, 2][-1]",
+            "var x = [1, 2][0.5]",
+            "var x = \"ab\"[-1]",
+            "var x = [1, 2]\nx[5] = 0",
+            "var x = \"ab\"\nx[-1] = \"c\"",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+
+    #[test]
+    fn halt_stops_program() {
+        let src = "
+var a = 1
+var ensured = false
+var caught = false
+fn stop() do
+    try do
+        halt()
+    catch do
+        caught = true
+    ensure do
+        ensured = true
+    end
+end
+stop()
+a = 2
+";
+        let (result, globals) = run(src);
+        assert!(result.is_ok());
+        let get = |name: &str| {
+            globals
+                .borrow()
+                .get(name, Cursor::new())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(get("a"), "1");
+        // halting isn't an error, so it skips catch but still runs ensure
+        assert_eq!(get("caught"), "false");
+        assert_eq!(get("ensured"), "true");
+    }
+
+    #[test]
+    fn try_catch_binds_error() {
+        let src = "
+var kind = Null
+var msg = Null
+var ensured = false
+try do
+    throw err(\"ValueErr\", \"boom\")
+catch e, v do
+    kind = e
+    msg = v
+ensure do
+    ensured = true
+end
+var thrown = Null
+try do
+    throw [1, 2]
+catch e, v do
+    thrown = e + \": \" + v.len().to_str()
+end
+";
+        assert_eq!(global(src, "kind").to_string(), "ValueErr");
+        assert_eq!(global(src, "msg").to_string(), "boom");
+        assert_eq!(global(src, "ensured").to_string(), "true");
+        assert_eq!(global(src, "thrown").to_string(), "UserErr: 2");
+    }
+
+    #[test]
+    fn try_does_not_catch_control_flow() {
+        let src = "
+fn f() do
+    try do
+        return 1
+    catch e do
+        return 2
+    end
+    return 3
+end
+var ret = f()
+var caught = false
+var i = 0
+while true do
+    try do
+        i += 1
+        break
+    catch e do
+        caught = true
+    end
+end
+";
+        assert_eq!(global(src, "ret").to_string(), "1");
+        assert_eq!(global(src, "i").to_string(), "1");
+        assert_eq!(global(src, "caught").to_string(), "false");
+    }
+
+    #[test]
+    fn list_equality() {
+        let src = "
+var a = [1, [2, \"x\"], [[3]]]
+var b = [1, [2, \"x\"], [[3]]]
+var same = a == b
+var diff = a == [1, [2, \"y\"], [[3]]]
+var shorter = a == [1, [2, \"x\"]]
+var cyc_a = [1]
+cyc_a.push(cyc_a)
+var cyc_b = [1]
+cyc_b.push(cyc_b)
+var cyclic = cyc_a == cyc_b
+";
+        assert_eq!(global(src, "same").to_string(), "true");
+        assert_eq!(global(src, "diff").to_string(), "false");
+        assert_eq!(global(src, "shorter").to_string(), "false");
+        assert_eq!(global(src, "cyclic").to_string(), "true");
+    }
+
+    #[test]
+    fn dict_equality() {
+        let src = "
+var d = {\"a\": 1, \"b\": [2]}
+var itself = d == d
+var wrapped = [d] == [d]
+var same = d == {\"b\": [2], \"a\": 1}
+var diff = d == {\"a\": 1, \"b\": [3]}
+var missing = d == {\"a\": 1, \"c\": [2]}
+var found = [1, d].contains({\"a\": 1, \"b\": [2]})
+var index = [1, d].index_of(d)
+var cyc_a = {}
+cyc_a[\"self\"] = cyc_a
+var cyc_b = {}
+cyc_b[\"self\"] = cyc_b
+var cyclic = cyc_a == cyc_b
+";
+        for (name, expected) in [
+            ("itself", "true"),
+            ("wrapped", "true"),
+            ("same", "true"),
+            ("diff", "false"),
+            ("missing", "false"),
+            ("found", "true"),
+            ("index", "1"),
+            ("cyclic", "true"),
+        ] {
+            assert_eq!(global(src, name).to_string(), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn instance_equality() {
+        let src = "
+obj Pos do
+    init(self, x, y) do
+        self.x = x
+        self.y = y
+    end
+end
+obj Vec do
+    init(self, x, y) do
+        self.x = x
+        self.y = y
+    end
+end
+var same = Pos(1, [2]) == Pos(1, [2])
+var diff = Pos(1, 2) == Pos(1, 3)
+var other_obj = Pos(1, 2) == Vec(1, 2)
+";
+        assert_eq!(global(src, "same").to_string(), "true");
+        assert_eq!(global(src, "diff").to_string(), "false");
+        assert_eq!(global(src, "other_obj").to_string(), "false");
+    }
+
+    #[test]
+    fn undefined_property_lists_props() {
+        let src = "
+obj Pos do
+    init(self, x, y) do
+        self.x = x
+        self.y = y
+    end
+    length(self) do
+        return self.x + self.y
+    end
+end
+var p = Pos(1, 2)
+p.lenght()
+";
+        let (result, _) = run(src);
+        match result {
+            Err(RuntimeEvent::Err(err)) => {
+                assert!(matches!(err.kind, ErrKind::Name));
+                assert_eq!(err.msg, "undefined property 'lenght'");
+                assert_eq!(
+                    err.note.as_deref(),
+                    Some("Pos has fields: x, y; methods: length")
+                );
+            }
+            _ => panic!("expected a NameErr"),
+        }
+    }
+}
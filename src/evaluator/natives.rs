@@ -1,11 +1,17 @@
+mod file;
+mod json;
 mod macros;
 mod math;
 mod p5;
 mod rand;
+mod string;
 mod sys;
 mod term;
 mod tui;
 
+pub use tui::reset as reset_tui;
+
+use ordered_float::OrderedFloat;
 use std::{
     cell::RefCell,
     io::{self, Write},
@@ -36,12 +42,45 @@ impl Natives {
         natives
             .borrow_mut()
             .define("println".into(), Value::Callable(Rc::new(FnPrintln)));
+        natives
+            .borrow_mut()
+            .define("eprint".into(), Value::Callable(Rc::new(FnEprint)));
+        natives
+            .borrow_mut()
+            .define("eprintln".into(), Value::Callable(Rc::new(FnEprintln)));
         natives
             .borrow_mut()
             .define("read".into(), Value::Callable(Rc::new(FnRead)));
         natives
             .borrow_mut()
             .define("err".into(), Value::Callable(Rc::new(FnErr)));
+        natives
+            .borrow_mut()
+            .define("len".into(), Value::Callable(Rc::new(FnLen)));
+        natives
+            .borrow_mut()
+            .define("type".into(), Value::Callable(Rc::new(FnType)));
+        natives
+            .borrow_mut()
+            .define("to_num".into(), Value::Callable(Rc::new(FnToNum)));
+        natives
+            .borrow_mut()
+            .define("to_str".into(), Value::Callable(Rc::new(FnToStr)));
+        natives
+            .borrow_mut()
+            .define("range".into(), Value::Callable(Rc::new(FnRange)));
+        natives
+            .borrow_mut()
+            .define("format".into(), Value::Callable(Rc::new(FnFormat)));
+        natives
+            .borrow_mut()
+            .define("halt".into(), Value::Callable(Rc::new(FnHalt)));
+        natives
+            .borrow_mut()
+            .define("assert".into(), Value::Callable(Rc::new(FnAssert)));
+        natives
+            .borrow_mut()
+            .define("assert_eq".into(), Value::Callable(Rc::new(FnAssertEq)));
 
         // global objects
         natives.borrow_mut().define("Sys".into(), sys::native_sys());
@@ -54,6 +93,15 @@ impl Natives {
         natives
             .borrow_mut()
             .define("Term".into(), term::native_term());
+        natives
+            .borrow_mut()
+            .define("File".into(), file::native_file());
+        natives
+            .borrow_mut()
+            .define("Json".into(), json::native_json());
+        natives
+            .borrow_mut()
+            .define("Str".into(), string::native_str());
         natives.borrow_mut().define("Tui".into(), tui::native_tui());
         natives.borrow_mut().define("P5".into(), p5::native_p5());
 
@@ -61,20 +109,44 @@ impl Natives {
     }
 }
 
-// print(expr)
-native_fn!(FnPrint, "print", 1, |_evaluator, args, _cursor| {
-    print!("{}", args[0]);
+// print(exprs...)
+native_fn!(FnPrint, "print", 0.., |_evaluator, args, _cursor| {
+    print!("{}", join_args(&args));
     Ok(Value::Null)
 });
 
-// println(expr)
-native_fn!(FnPrintln, "println", 1, |_evaluator, args, _cursor| {
-    println!("{}", args[0]);
+// println(exprs...)
+native_fn!(FnPrintln, "println", 0.., |_evaluator, args, _cursor| {
+    println!("{}", join_args(&args));
     Ok(Value::Null)
 });
 
-// read() -> Str
-native_fn!(FnRead, "read", 0, |_evaluator, _args, cursor| {
+// eprint(exprs...): same as print but writes to stderr
+native_fn!(FnEprint, "eprint", 0.., |_evaluator, args, _cursor| {
+    eprint!("{}", join_args(&args));
+    Ok(Value::Null)
+});
+
+// eprintln(exprs...): same as println but writes to stderr
+native_fn!(FnEprintln, "eprintln", 0.., |_evaluator, args, _cursor| {
+    eprintln!("{}", join_args(&args));
+    Ok(Value::Null)
+});
+
+// Display values separated by single spaces, used by print(), println(), eprint() and
+// eprintln()
+fn join_args(args: &[Value]) -> String {
+    args.iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// read(prompt?) -> Str
+native_fn!(FnRead, "read", 0..=1, |_evaluator, args, cursor| {
+    if let Some(prompt) = args.first() {
+        print!("{}", prompt);
+    }
     io::stdout().flush().map_err(|err| {
         RuntimeEvent::error(
             ErrKind::IO,
@@ -101,3 +173,168 @@ native_fn!(FnErr, "err", 2, |_evaluator, args, cursor| {
         cursor,
     )))
 });
+
+// halt(): stops the program without an error, unlike Sys.exit() ensure blocks still run
+native_fn!(FnHalt, "halt", 0, |_evaluator, _args, _cursor| {
+    Err(RuntimeEvent::Halt)
+});
+
+// assert(cond, msg?): throws a ValueErr if cond is falsey
+native_fn!(FnAssert, "assert", 1..=2, |_evaluator, args, cursor| {
+    if args[0].is_truthy() {
+        return Ok(Value::Null);
+    }
+
+    let msg = match args.get(1) {
+        Some(msg) => format!("assertion failed: {}", msg),
+        None => "assertion failed".to_string(),
+    };
+    Err(RuntimeEvent::error_with_note(
+        ErrKind::Value,
+        msg,
+        format!("the condition evaluated to {}", args[0]),
+        cursor,
+    ))
+});
+
+// assert_eq(actual, expected): throws a ValueErr if the values aren't equal
+native_fn!(FnAssertEq, "assert_eq", 2, |_evaluator, args, cursor| {
+    if args[0].is_equal(&args[1]) {
+        return Ok(Value::Null);
+    }
+
+    Err(RuntimeEvent::error_with_note(
+        ErrKind::Value,
+        format!(
+            "assertion failed: expected {}, got {}",
+            args[1].to_quoted_string(),
+            args[0].to_quoted_string()
+        ),
+        "assert_eq(actual, expected) compares its arguments with ==".into(),
+        cursor,
+    ))
+});
+
+// len(val) -> Num: number of characters in a Str, elements in a List or pairs in a Dict
+native_fn!(FnLen, "len", 1, |_evaluator, args, cursor| {
+    let len = match &args[0] {
+        Value::Str(s) => s.borrow().chars().count(),
+        Value::List(list) => list.borrow().len(),
+        Value::Dict(dict) => dict.borrow().len(),
+        other => {
+            return Err(RuntimeEvent::error(
+                ErrKind::Type,
+                format!("expected Str, List or Dict, found {}", other.get_type()),
+                cursor,
+            ));
+        }
+    };
+    Ok(Value::Num(OrderedFloat(len as f64)))
+});
+
+// type(val) -> Str: type name of the value, objects report their own name
+native_fn!(FnType, "type", 1, |_evaluator, args, _cursor| {
+    let name = match &args[0] {
+        Value::Obj(obj) => obj.name.clone(),
+        other => other.get_type(),
+    };
+    Ok(Value::Str(Rc::new(RefCell::new(name))))
+});
+
+// to_num(val) -> Num: parses a Str into a Num, Nums are returned as is and Bools become 1 or 0
+native_fn!(FnToNum, "to_num", 1, |_evaluator, args, cursor| {
+    match &args[0] {
+        Value::Num(n) => Ok(Value::Num(*n)),
+        Value::Bool(b) => Ok(Value::Num(OrderedFloat(if *b { 1. } else { 0. }))),
+        Value::Str(s) => match s.borrow().trim().parse::<f64>() {
+            Ok(n) => Ok(Value::Num(OrderedFloat(n))),
+            Err(_) => Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!("can't convert \"{}\" to Num", s.borrow()),
+                cursor,
+            )),
+        },
+        other => Err(RuntimeEvent::error(
+            ErrKind::Type,
+            format!("expected Num, Bool or Str, found {}", other.get_type()),
+            cursor,
+        )),
+    }
+});
+
+// to_str(val) -> Str: the value as it would be printed
+native_fn!(FnToStr, "to_str", 1, |_evaluator, args, _cursor| {
+    Ok(Value::Str(Rc::new(RefCell::new(args[0].to_string()))))
+});
+
+// range(start, end, step?) -> List
+native_fn!(FnRange, "range", 2..=3, |_evaluator, args, cursor| {
+    let start = args[0].check_num(cursor, Some("start".into()))?;
+    let end = args[1].check_num(cursor, Some("end".into()))?;
+    let step = match args.get(2) {
+        Some(val) => val.check_num(cursor, Some("step".into()))?,
+        None => 1.,
+    };
+
+    if step == 0. {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            "range step can't be 0".into(),
+            cursor,
+        ));
+    }
+
+    let mut values = vec![];
+    let mut i = start;
+    while (step > 0. && i < end) || (step < 0. && i > end) {
+        values.push(Value::Num(OrderedFloat(i)));
+        i += step;
+    }
+
+    Ok(Value::List(Rc::new(RefCell::new(values))))
+});
+
+// format(template, args...) -> Str: replaces each {} with the next argument, {{ and }} are
+// literal braces
+native_fn!(FnFormat, "format", 1.., |_evaluator, args, cursor| {
+    let template = args[0].check_str(cursor, Some("format template".into()))?;
+    let template = template.borrow();
+    let mut values = args[1..].iter();
+    let format_err = |msg: &str| RuntimeEvent::error(ErrKind::Value, msg.into(), cursor);
+
+    let mut out = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                out.push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                match values.next() {
+                    Some(val) => out.push_str(&val.to_string()),
+                    None => {
+                        return Err(format_err(
+                            "format template has more placeholders than arguments",
+                        ));
+                    }
+                }
+            }
+            ('{', _) | ('}', _) => {
+                return Err(format_err(&format!(
+                    "unmatched '{}' in format template, use '{}{}' for a literal brace",
+                    c, c, c
+                )));
+            }
+            _ => out.push(c),
+        }
+    }
+    if values.next().is_some() {
+        return Err(format_err(
+            "format template has fewer placeholders than arguments",
+        ));
+    }
+
+    Ok(Value::Str(Rc::new(RefCell::new(out))))
+});
@@ -0,0 +1,143 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    rc::Rc,
+};
+
+use crate::{
+    evaluator::{
+        Callable, EvalResult, Evaluator,
+        object::{Method, NativeMethod, Object},
+        runtime_err::RuntimeEvent,
+        value::Value,
+    },
+    lexer::cursor::Cursor,
+    native_fn,
+};
+
+pub fn native_file() -> Value {
+    let mut methods: HashMap<String, Method> = HashMap::new();
+
+    methods.insert(
+        "read".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnFileRead), false)),
+    );
+    methods.insert(
+        "write".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnFileWrite), false)),
+    );
+    methods.insert(
+        "append".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnFileAppend), false)),
+    );
+    methods.insert(
+        "exists".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnFileExists), false)),
+    );
+
+    Value::Obj(Rc::new(Object::new("File".into(), methods)))
+}
+
+// Convert an io::Error into an IOErr located at the call site
+fn io_err(cursor: Cursor) -> impl Fn(io::Error) -> RuntimeEvent {
+    move |err| match RuntimeEvent::from(err) {
+        RuntimeEvent::Err(err) => RuntimeEvent::Err(err.cursor(cursor)),
+        event => event,
+    }
+}
+
+// read(path: Str) -> Str
+native_fn!(FnFileRead, "file_read", 1, |_evaluator, args, cursor| {
+    let path = args[0].check_str(cursor, Some("path".into()))?;
+    let text = fs::read_to_string(path.borrow().as_str()).map_err(io_err(cursor))?;
+    Ok(Value::Str(Rc::new(RefCell::new(text))))
+});
+
+// write(path: Str, text: Str): creates or overwrites the file
+native_fn!(FnFileWrite, "file_write", 2, |_evaluator, args, cursor| {
+    let path = args[0].check_str(cursor, Some("path".into()))?;
+    let text = args[1].check_str(cursor, Some("text".into()))?;
+    fs::write(path.borrow().as_str(), text.borrow().as_bytes()).map_err(io_err(cursor))?;
+    Ok(Value::Null)
+});
+
+// append(path: Str, text: Str): creates the file if it doesn't exist
+native_fn!(
+    FnFileAppend,
+    "file_append",
+    2,
+    |_evaluator, args, cursor| {
+        let path = args[0].check_str(cursor, Some("path".into()))?;
+        let text = args[1].check_str(cursor, Some("text".into()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.borrow().as_str())
+            .map_err(io_err(cursor))?;
+        file.write_all(text.borrow().as_bytes())
+            .map_err(io_err(cursor))?;
+        Ok(Value::Null)
+    }
+);
+
+// exists(path: Str) -> Bool
+native_fn!(
+    FnFileExists,
+    "file_exists",
+    1,
+    |_evaluator, args, cursor| {
+        let path = args[0].check_str(cursor, Some("path".into()))?;
+        Ok(Value::Bool(Path::new(path.borrow().as_str()).exists()))
+    }
+);
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::evaluator::{
+        runtime_err::ErrKind,
+        tests::{assert_err_kind, global, run},
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("quetite-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn write_then_read() {
+        let dir = temp_dir("write_then_read");
+        let path = dir.join("out.txt");
+        let src = format!(
+            "
+File.write({path:?}, \"hello\\n\")
+File.append({path:?}, \"world\")
+var exists = File.exists({path:?})
+var text = File.read({path:?})
+",
+        );
+        assert_eq!(global(&src, "exists").to_string(), "true");
+        assert_eq!(global(&src, "text").to_string(), "hello\nworld");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file() {
+        let dir = temp_dir("missing_file");
+        let path = dir.join("missing.txt");
+        let src = format!("var exists = File.exists({path:?})");
+        assert_eq!(global(&src, "exists").to_string(), "false");
+
+        let (result, _) = run(&format!("File.read({path:?})"));
+        assert_err_kind(result, ErrKind::IO);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
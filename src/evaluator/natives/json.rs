@@ -0,0 +1,268 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use indexmap::IndexMap;
+use ordered_float::OrderedFloat;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::{Error as _, SerializeMap, SerializeSeq},
+};
+
+use crate::{
+    evaluator::{
+        Callable, EvalResult, Evaluator,
+        object::{Method, NativeMethod, Object},
+        runtime_err::{ErrKind, RuntimeEvent},
+        value::{Value, ValueKey},
+    },
+    native_fn,
+};
+
+pub fn native_json() -> Value {
+    let mut methods: HashMap<String, Method> = HashMap::new();
+
+    methods.insert(
+        "parse".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnJsonParse), false)),
+    );
+    methods.insert(
+        "stringify".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnJsonStringify), false)),
+    );
+
+    Value::Obj(Rc::new(Object::new("Json".into(), methods)))
+}
+
+// parse(text: Str) -> Value: objects become Dicts with their keys in the original order
+native_fn!(FnJsonParse, "json_parse", 1, |_evaluator, args, cursor| {
+    let text = args[0].check_str(cursor, Some("JSON text".into()))?;
+    let parsed: JsonIn = serde_json::from_str(text.borrow().as_str()).map_err(|err| {
+        // serde_json appends the position to its messages, it's moved to the front instead
+        let position = format!(" at line {} column {}", err.line(), err.column());
+        let msg = err.to_string();
+        RuntimeEvent::error(
+            ErrKind::Value,
+            format!(
+                "invalid JSON at line {}, column {}: {}",
+                err.line(),
+                err.column(),
+                msg.strip_suffix(&position).unwrap_or(&msg)
+            ),
+            cursor,
+        )
+    })?;
+    Ok(parsed.0)
+});
+
+// stringify(val, pretty?: Bool) -> Str
+native_fn!(
+    FnJsonStringify,
+    "json_stringify",
+    1..=2,
+    |_evaluator, args, cursor| {
+        let pretty = match args.get(1) {
+            Some(val) => val.check_bool(cursor, Some("pretty flag".into()))?,
+            None => false,
+        };
+        if contains_itself(&args[0], &mut Vec::new()) {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                "can't convert a List or Dict that contains itself to JSON".into(),
+                cursor,
+            ));
+        }
+        let val = JsonOut(&args[0]);
+        let text = if pretty {
+            serde_json::to_string_pretty(&val)
+        } else {
+            serde_json::to_string(&val)
+        };
+        let text =
+            text.map_err(|err| RuntimeEvent::error(ErrKind::Type, err.to_string(), cursor))?;
+        Ok(Value::Str(Rc::new(RefCell::new(text))))
+    }
+);
+
+/// Deserializes any JSON value straight into a Value, keeping the key order of objects
+struct JsonIn(Value);
+
+impl<'de> Deserialize<'de> for JsonIn {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonVisitor).map(JsonIn)
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_bool<E>(self, b: bool) -> Result<Value, E> {
+        Ok(Value::Bool(b))
+    }
+
+    fn visit_i64<E>(self, n: i64) -> Result<Value, E> {
+        Ok(Value::Num(OrderedFloat(n as f64)))
+    }
+
+    fn visit_u64<E>(self, n: u64) -> Result<Value, E> {
+        Ok(Value::Num(OrderedFloat(n as f64)))
+    }
+
+    fn visit_f64<E>(self, n: f64) -> Result<Value, E> {
+        Ok(Value::Num(OrderedFloat(n)))
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Value, E> {
+        Ok(Value::Str(Rc::new(RefCell::new(s.to_string()))))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(JsonIn(item)) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::List(Rc::new(RefCell::new(items))))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = IndexMap::new();
+        while let Some((key, JsonIn(val))) = map.next_entry::<String, JsonIn>()? {
+            entries.insert(ValueKey::Str(key), val);
+        }
+        Ok(Value::Dict(Rc::new(RefCell::new(entries))))
+    }
+}
+
+/// Serializes a Value as JSON, Dict keys that aren't Strs are written as their display text
+struct JsonOut<'a>(&'a Value);
+
+impl Serialize for JsonOut<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Null => serializer.serialize_unit(),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::Num(n) if n.0.is_finite() && n.0.fract() == 0.0 && n.0.abs() < 1e15 => {
+                serializer.serialize_i64(n.0 as i64)
+            }
+            Value::Num(n) => serializer.serialize_f64(n.0),
+            Value::Str(s) => serializer.serialize_str(&s.borrow()),
+            Value::List(list) => {
+                let list = list.borrow();
+                let mut seq = serializer.serialize_seq(Some(list.len()))?;
+                for item in list.iter() {
+                    seq.serialize_element(&JsonOut(item))?;
+                }
+                seq.end()
+            }
+            Value::Dict(dict) => {
+                let dict = dict.borrow();
+                let mut map = serializer.serialize_map(Some(dict.len()))?;
+                for (key, val) in dict.iter() {
+                    let key = match key {
+                        ValueKey::Str(s) => s.clone(),
+                        other => Into::<Value>::into(other.clone()).to_string(),
+                    };
+                    map.serialize_entry(&key, &JsonOut(val))?;
+                }
+                map.end()
+            }
+            other => Err(S::Error::custom(format!(
+                "can't convert a value of type {} to JSON",
+                other.get_type()
+            ))),
+        }
+    }
+}
+
+/// Whether `val` is nested in itself, `parents` holds the lists and dicts currently being
+/// walked so a cycle is caught instead of recursing forever
+fn contains_itself(val: &Value, parents: &mut Vec<usize>) -> bool {
+    let ptr = match val {
+        Value::List(list) => Rc::as_ptr(list) as usize,
+        Value::Dict(dict) => Rc::as_ptr(dict) as usize,
+        _ => return false,
+    };
+    if parents.contains(&ptr) {
+        return true;
+    }
+
+    parents.push(ptr);
+    let found = match val {
+        Value::List(list) => list
+            .borrow()
+            .iter()
+            .any(|item| contains_itself(item, parents)),
+        Value::Dict(dict) => dict
+            .borrow()
+            .values()
+            .any(|item| contains_itself(item, parents)),
+        _ => false,
+    };
+    parents.pop();
+    found
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::{
+        runtime_err::{ErrKind, RuntimeErr, RuntimeEvent},
+        tests::{assert_err_kind, eval, global, run},
+    };
+
+    #[test]
+    fn round_trips() {
+        for json in [
+            r#"{"b":1,"a":[true,null,"s"]}"#,
+            r#"[1,2.5,-3,[],{}]"#,
+            r#"{"outer":{"inner":[{"x":1},{"y":[2,3]}]}}"#,
+            r#""text with \"quotes\"""#,
+        ] {
+            let src = format!("var x = Json.stringify(Json.parse({:?}))", json);
+            assert_eq!(global(&src, "x").to_string(), json);
+        }
+
+        assert_eq!(eval("Json.parse(\"[1, 2]\")[1]"), "2");
+        assert_eq!(eval("Json.parse(\"{\\\"a\\\": 1}\")[\"a\"]"), "1");
+        assert_eq!(eval("Json.stringify({1: [1]})"), r#"{"1":[1]}"#);
+        assert_eq!(eval("Json.stringify([1], true)"), "[\n  1\n]");
+    }
+
+    #[test]
+    fn errors() {
+        let (result, _) = run("Json.parse(\"{\\\"a\\\": }\")");
+        match result {
+            Err(RuntimeEvent::Err(RuntimeErr {
+                kind: ErrKind::Value,
+                msg,
+                ..
+            })) => assert_eq!(msg, "invalid JSON at line 1, column 7: expected value"),
+            _ => panic!("expected a ValueErr"),
+        }
+
+        for src in [
+            "var l = []\nl.push(l)\nJson.stringify(l)",
+            "var d = {}\nd[\"self\"] = [d]\nJson.stringify(d)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+        // the same List twice isn't a cycle
+        assert_eq!(
+            global("var l = [1]\nvar x = Json.stringify([l, l])", "x").to_string(),
+            "[[1],[1]]"
+        );
+
+        let (result, _) = run("Json.stringify([print])");
+        assert_err_kind(result, ErrKind::Type);
+    }
+}
@@ -1,5 +1,52 @@
 #[macro_export]
 macro_rules! native_fn {
+    ($name:ident, $str_name:expr, $min_arity:literal..=$arity:literal, |$evaluator:ident, $args:ident, $cursor:ident| $body:block) => {
+        #[derive(Debug)]
+        pub struct $name;
+        impl Callable for $name {
+            fn name(&self) -> &str {
+                $str_name
+            }
+            fn arity(&self) -> usize {
+                $arity
+            }
+            fn min_arity(&self) -> usize {
+                $min_arity
+            }
+            fn call(
+                &self,
+                $evaluator: &mut Evaluator,
+                $args: Vec<Value>,
+                $cursor: crate::lexer::cursor::Cursor,
+            ) -> EvalResult<Value> {
+                crate::evaluator::runtime_err::at_call_site($cursor, move || $body)
+            }
+        }
+    };
+    // variadic, any number of arguments from $min_arity up
+    ($name:ident, $str_name:expr, $min_arity:literal.., |$evaluator:ident, $args:ident, $cursor:ident| $body:block) => {
+        #[derive(Debug)]
+        pub struct $name;
+        impl Callable for $name {
+            fn name(&self) -> &str {
+                $str_name
+            }
+            fn arity(&self) -> usize {
+                usize::MAX
+            }
+            fn min_arity(&self) -> usize {
+                $min_arity
+            }
+            fn call(
+                &self,
+                $evaluator: &mut Evaluator,
+                $args: Vec<Value>,
+                $cursor: crate::lexer::cursor::Cursor,
+            ) -> EvalResult<Value> {
+                crate::evaluator::runtime_err::at_call_site($cursor, move || $body)
+            }
+        }
+    };
     ($name:ident, $str_name:expr, $arity:expr, |$evaluator:ident, $args:ident, $cursor:ident| $body:block) => {
         #[derive(Debug)]
         pub struct $name;
@@ -16,7 +63,7 @@ macro_rules! native_fn {
                 $args: Vec<Value>,
                 $cursor: crate::lexer::cursor::Cursor,
             ) -> EvalResult<Value> {
-                $body
+                crate::evaluator::runtime_err::at_call_site($cursor, move || $body)
             }
         }
     };
@@ -44,7 +91,7 @@ macro_rules! native_fn_with_data {
                 $cursor: crate::lexer::cursor::Cursor,
             ) -> EvalResult<Value> {
                 let $data = &self.data;
-                $body
+                crate::evaluator::runtime_err::at_call_site($cursor, move || $body)
             }
         }
 
@@ -78,7 +125,7 @@ macro_rules! native_fn_with_val {
                 $cursor: crate::lexer::cursor::Cursor,
             ) -> EvalResult<Value> {
                 let $val = &self.val;
-                $body
+                crate::evaluator::runtime_err::at_call_site($cursor, move || $body)
             }
         }
 
@@ -13,6 +13,7 @@ use crate::{
         runtime_err::{ErrKind, RuntimeEvent},
         value::Value,
     },
+    lexer::cursor::Cursor,
     native_fn,
 };
 
@@ -49,6 +50,14 @@ pub fn native_math() -> Value {
         "atan2".into(),
         Method::Native(NativeMethod::new(Rc::new(FnMathAtan2), false)),
     );
+    methods.insert(
+        "deg".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathDeg), false)),
+    );
+    methods.insert(
+        "rad".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathRad), false)),
+    );
     methods.insert(
         "sqrt".into(),
         Method::Native(NativeMethod::new(Rc::new(FnMathSqrt), false)),
@@ -81,6 +90,62 @@ pub fn native_math() -> Value {
         "hypot".into(),
         Method::Native(NativeMethod::new(Rc::new(FnMathHypot), false)),
     );
+    methods.insert(
+        "dist".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathDist), false)),
+    );
+    methods.insert(
+        "lerp".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathLerp), false)),
+    );
+    methods.insert(
+        "abs".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathAbs), false)),
+    );
+    methods.insert(
+        "floor".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathFloor), false)),
+    );
+    methods.insert(
+        "ceil".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathCeil), false)),
+    );
+    methods.insert(
+        "round".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathRound), false)),
+    );
+    methods.insert(
+        "round_to".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathRoundTo), false)),
+    );
+    methods.insert(
+        "floor_to".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathFloorTo), false)),
+    );
+    methods.insert(
+        "trunc".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathTrunc), false)),
+    );
+    methods.insert(
+        "is_int".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathIsInt), false)),
+    );
+    methods.insert(
+        "min".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathMin), false)),
+    );
+    methods.insert(
+        "max".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathMax), false)),
+    );
+    methods.insert(
+        "clamp".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathClamp), false)),
+    );
+    methods.insert(
+        "map".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnMathMap), false)),
+    );
     methods.insert(
         "pi".into(),
         Method::Native(NativeMethod::new(Rc::new(FnMathPi), false)),
@@ -94,7 +159,14 @@ pub fn native_math() -> Value {
         Method::Native(NativeMethod::new(Rc::new(FnMathE), false)),
     );
 
-    Value::Obj(Rc::new(Object::new("Math".into(), methods)))
+    let mut consts: HashMap<String, Value> = HashMap::new();
+    consts.insert("PI".into(), Value::Num(OrderedFloat(PI)));
+    consts.insert("TAU".into(), Value::Num(OrderedFloat(TAU)));
+    consts.insert("E".into(), Value::Num(OrderedFloat(E)));
+
+    Value::Obj(Rc::new(
+        Object::new("Math".into(), methods).with_consts(consts),
+    ))
 }
 
 // sin(x) -> Num
@@ -140,6 +212,18 @@ native_fn!(FnMathAtan2, "atan2", 2, |_evaluator, args, cursor| {
     Ok(Value::Num(OrderedFloat(y.atan2(x))))
 });
 
+// deg(rad) -> Num: radians to degrees
+native_fn!(FnMathDeg, "deg", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("radians".into()))?;
+    Ok(Value::Num(OrderedFloat(x.to_degrees())))
+});
+
+// rad(deg) -> Num: degrees to radians
+native_fn!(FnMathRad, "rad", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("degrees".into()))?;
+    Ok(Value::Num(OrderedFloat(x.to_radians())))
+});
+
 // sqrt(x) -> Num
 native_fn!(FnMathSqrt, "sqrt", 1, |_evaluator, args, cursor| {
     let x = args[0].check_num(cursor, Some("argument".into()))?;
@@ -219,6 +303,148 @@ native_fn!(FnMathHypot, "hypot", 2, |_evaluator, args, cursor| {
     Ok(Value::Num(OrderedFloat(a.hypot(b))))
 });
 
+// dist(x1, y1, x2, y2) -> Num: Euclidean distance between two points
+native_fn!(FnMathDist, "dist", 4, |_evaluator, args, cursor| {
+    let x1 = args[0].check_num(cursor, Some("x1".into()))?;
+    let y1 = args[1].check_num(cursor, Some("y1".into()))?;
+    let x2 = args[2].check_num(cursor, Some("x2".into()))?;
+    let y2 = args[3].check_num(cursor, Some("y2".into()))?;
+    Ok(Value::Num(OrderedFloat((x2 - x1).hypot(y2 - y1))))
+});
+
+// lerp(a, b, t) -> Num: a at t = 0 and b at t = 1, t isn't clamped so values outside [0, 1]
+// extrapolate
+native_fn!(FnMathLerp, "lerp", 3, |_evaluator, args, cursor| {
+    let a = args[0].check_num(cursor, Some("a".into()))?;
+    let b = args[1].check_num(cursor, Some("b".into()))?;
+    let t = args[2].check_num(cursor, Some("t".into()))?;
+    Ok(Value::Num(OrderedFloat(a + (b - a) * t)))
+});
+
+// abs(x) -> Num
+native_fn!(FnMathAbs, "abs", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    Ok(Value::Num(OrderedFloat(x.abs())))
+});
+
+// floor(x) -> Num
+native_fn!(FnMathFloor, "floor", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    Ok(Value::Num(OrderedFloat(x.floor())))
+});
+
+// ceil(x) -> Num
+native_fn!(FnMathCeil, "ceil", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    Ok(Value::Num(OrderedFloat(x.ceil())))
+});
+
+// round(x) -> Num
+native_fn!(FnMathRound, "round", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    Ok(Value::Num(OrderedFloat(x.round())))
+});
+
+// round_to(x, places) -> Num: x rounded to `places` decimals, halfway values round away from 0
+native_fn!(FnMathRoundTo, "round_to", 2, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    let places = args[1].check_num(cursor, Some("decimal places".into()))?;
+    Ok(Value::Num(OrderedFloat(to_places(
+        x,
+        places,
+        f64::round,
+        "Math.round_to",
+        cursor,
+    )?)))
+});
+
+// floor_to(x, places) -> Num: x rounded down to `places` decimals
+native_fn!(FnMathFloorTo, "floor_to", 2, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    let places = args[1].check_num(cursor, Some("decimal places".into()))?;
+    Ok(Value::Num(OrderedFloat(to_places(
+        x,
+        places,
+        f64::floor,
+        "Math.floor_to",
+        cursor,
+    )?)))
+});
+
+// Apply `op` to x scaled by 10^places, x is returned as is if it's too precise to scale
+pub(super) fn to_places(
+    x: f64,
+    places: f64,
+    op: fn(f64) -> f64,
+    name: &str,
+    cursor: Cursor,
+) -> EvalResult<f64> {
+    if places < 0.0 || places.fract() != 0.0 {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!("{} expects decimal places to be a whole number >= 0", name),
+            cursor,
+        ));
+    }
+
+    let scale = 10f64.powf(places);
+    let scaled = x * scale;
+    if !scaled.is_finite() {
+        return Ok(x);
+    }
+    Ok(op(scaled) / scale)
+}
+
+// trunc(x) -> Num: x with the fractional part dropped, rounds toward zero
+native_fn!(FnMathTrunc, "trunc", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    Ok(Value::Num(OrderedFloat(x.trunc())))
+});
+
+// is_int(x) -> Bool: true if x is a finite number without a fractional part
+native_fn!(FnMathIsInt, "is_int", 1, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("argument".into()))?;
+    Ok(Value::Bool(x.is_finite() && x.fract() == 0.0))
+});
+
+// min(a, b) -> Num
+native_fn!(FnMathMin, "min", 2, |_evaluator, args, cursor| {
+    let a = args[0].check_num(cursor, Some("a".into()))?;
+    let b = args[1].check_num(cursor, Some("b".into()))?;
+    Ok(Value::Num(OrderedFloat(a.min(b))))
+});
+
+// max(a, b) -> Num
+native_fn!(FnMathMax, "max", 2, |_evaluator, args, cursor| {
+    let a = args[0].check_num(cursor, Some("a".into()))?;
+    let b = args[1].check_num(cursor, Some("b".into()))?;
+    Ok(Value::Num(OrderedFloat(a.max(b))))
+});
+
+// clamp(x, lo, hi) -> Num: x limited to [lo, hi], the bounds can be given in either order
+native_fn!(FnMathClamp, "clamp", 3, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("x".into()))?;
+    let lo = args[1].check_num(cursor, Some("lo".into()))?;
+    let hi = args[2].check_num(cursor, Some("hi".into()))?;
+    let (lo, hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+    Ok(Value::Num(OrderedFloat(x.max(lo).min(hi))))
+});
+
+// map(x, in_lo, in_hi, out_lo, out_hi) -> Num: linearly maps x from one range to another,
+// the result isn't clamped to the output range. A zero width input range maps to out_lo
+native_fn!(FnMathMap, "map", 5, |_evaluator, args, cursor| {
+    let x = args[0].check_num(cursor, Some("x".into()))?;
+    let in_lo = args[1].check_num(cursor, Some("in_lo".into()))?;
+    let in_hi = args[2].check_num(cursor, Some("in_hi".into()))?;
+    let out_lo = args[3].check_num(cursor, Some("out_lo".into()))?;
+    let out_hi = args[4].check_num(cursor, Some("out_hi".into()))?;
+    if in_hi == in_lo {
+        return Ok(Value::Num(OrderedFloat(out_lo)));
+    }
+    let t = (x - in_lo) / (in_hi - in_lo);
+    Ok(Value::Num(OrderedFloat(out_lo + t * (out_hi - out_lo))))
+});
+
 // pi() -> Num
 native_fn!(FnMathPi, "pi", 0, |_evaluator, _args, _cursor| {
     Ok(Value::Num(OrderedFloat(PI)))
@@ -233,3 +459,127 @@ native_fn!(FnMathTau, "tau", 0, |_evaluator, _args, _cursor| {
 native_fn!(FnMathE, "e", 0, |_evaluator, _args, _cursor| {
     Ok(Value::Num(OrderedFloat(E)))
 });
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::native_math;
+    use crate::evaluator::{
+        runtime_err::ErrKind,
+        tests::{assert_err_kind, eval, run},
+        value::{Value, format_num},
+    };
+
+    #[test]
+    fn object_name() {
+        match native_math() {
+            Value::Obj(obj) => assert_eq!(obj.name, "Math"),
+            _ => panic!("expected an Obj"),
+        }
+        assert_eq!(eval("Math"), "Math");
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(eval("Math.abs(-2.5)"), "2.5");
+        assert_eq!(eval("Math.floor(3.7)"), "3");
+        assert_eq!(eval("Math.floor(-3.2)"), "-4");
+        assert_eq!(eval("Math.ceil(3.2)"), "4");
+        assert_eq!(eval("Math.round(3.5)"), "4");
+        assert_eq!(eval("Math.round(3.4)"), "3");
+        assert_eq!(eval("Math.trunc(-3.7)"), "-3");
+        assert_eq!(eval("Math.trunc(3.7)"), "3");
+    }
+
+    #[test]
+    fn rounding_to_places() {
+        assert_eq!(eval("Math.round_to(3.14159, 2)"), "3.14");
+        assert_eq!(eval("Math.round_to(3.14159, 3)"), "3.142");
+        // halfway values round away from zero
+        assert_eq!(eval("Math.round_to(2.5, 0)"), "3");
+        assert_eq!(eval("Math.round_to(-2.5, 0)"), "-3");
+        assert_eq!(eval("Math.floor_to(3.14159, 2)"), "3.14");
+        assert_eq!(eval("Math.floor_to(3.999, 1)"), "3.9");
+        assert_eq!(eval("Math.floor_to(-3.14159, 2)"), "-3.15");
+        assert_eq!(eval("Math.round_to(1.25, 400)"), "1.25");
+
+        for src in ["Math.round_to(1.5, -1)", "Math.floor_to(1.5, 0.5)"] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+
+    #[test]
+    fn is_int() {
+        assert_eq!(eval("Math.is_int(3.0)"), "true");
+        assert_eq!(eval("Math.is_int(-4)"), "true");
+        assert_eq!(eval("Math.is_int(3.5)"), "false");
+        assert_eq!(eval("Math.is_int(1 / 0)"), "false");
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(eval("Math.min(2, -1)"), "-1");
+        assert_eq!(eval("Math.max(2, -1)"), "2");
+    }
+
+    #[test]
+    fn powers_and_logs() {
+        assert_eq!(eval("Math.sqrt(9)"), "3");
+        assert_eq!(eval("Math.pow(2, 10)"), "1024");
+        assert_eq!(eval("Math.exp(0)"), "1");
+        assert_eq!(eval("Math.log(8, 2)"), "3");
+        assert_eq!(eval("Math.atan2(0, 1)"), "0");
+    }
+
+    #[test]
+    fn trig() {
+        assert_eq!(eval("Math.tan(0)"), "0");
+        assert_eq!(eval("Math.asin(1) == Math.PI / 2"), "true");
+        assert_eq!(eval("Math.acos(1)"), "0");
+        assert_eq!(eval("Math.atan(0)"), "0");
+        assert_eq!(eval("Math.deg(Math.PI)"), "180");
+        assert_eq!(eval("Math.rad(180) == Math.PI"), "true");
+        assert_eq!(eval("Math.deg(Math.rad(45))"), "45");
+        // outside of [-1, 1] there's no inverse sine
+        assert_eq!(eval("Math.asin(2)"), "NaN");
+    }
+
+    #[test]
+    fn geometry() {
+        assert_eq!(eval("Math.hypot(3, 4)"), "5");
+        assert_eq!(eval("Math.dist(0, 0, 3, 4)"), "5");
+        assert_eq!(eval("Math.dist(1, 1, -2, -3)"), "5");
+        assert_eq!(eval("Math.lerp(0, 10, 0.5)"), "5");
+        assert_eq!(eval("Math.lerp(10, 20, 0)"), "10");
+        assert_eq!(eval("Math.lerp(0, 10, 1.5)"), "15");
+
+        let (result, _) = run("Math.dist(0, 0, \"3\", 4)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(eval("Math.PI"), format_num(std::f64::consts::PI));
+        assert_eq!(eval("Math.E"), format_num(std::f64::consts::E));
+        assert_eq!(eval("Math.PI == 3.141592653589793"), "true");
+        assert_eq!(eval("Math.PI == Math.pi()"), "true");
+    }
+
+    #[test]
+    fn non_num_is_type_err() {
+        let (result, _) = run("var x = Math.sqrt(\"9\")");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn clamp_and_map() {
+        assert_eq!(eval("Math.clamp(12, 0, 10)"), "10");
+        assert_eq!(eval("Math.clamp(-1, 0, 10)"), "0");
+        assert_eq!(eval("Math.clamp(5, 10, 0)"), "5");
+        assert_eq!(eval("Math.map(5, 0, 10, 0, 100)"), "50");
+        assert_eq!(eval("Math.map(15, 0, 10, 0, 100)"), "150");
+        assert_eq!(eval("Math.map(2, 0, 10, 100, 0)"), "80");
+        assert_eq!(eval("Math.map(3, 1, 1, 7, 9)"), "7");
+    }
+}
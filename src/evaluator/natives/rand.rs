@@ -1,7 +1,12 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use ordered_float::OrderedFloat;
-use rand::Rng;
+use rand::{
+    Rng, SeedableRng,
+    distr::{Distribution, weighted::WeightedIndex},
+    rngs::StdRng,
+    seq::{SliceRandom, index},
+};
 
 use crate::{
     evaluator::{
@@ -16,6 +21,15 @@ use crate::{
 const RAND_STRING_CHARSET: &[u8] =
     b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
 
+// Thread-local RNG, seeded from the OS until Rand.seed() is called
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_rng(&mut rand::rng()));
+}
+
+fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
 pub fn native_rand() -> Value {
     let mut methods: HashMap<String, Method> = HashMap::new();
 
@@ -27,9 +41,18 @@ pub fn native_rand() -> Value {
         "bool".into(),
         Method::Native(NativeMethod::new(Rc::new(FnRandBool), false)),
     );
+    methods.insert(
+        "weighted".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnRandWeighted), false)),
+    );
+    methods.insert(
+        "choice".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnRandChoice), false)),
+    );
+    // kept as an alias of choice
     methods.insert(
         "list".into(),
-        Method::Native(NativeMethod::new(Rc::new(FnRandList), false)),
+        Method::Native(NativeMethod::new(Rc::new(FnRandChoice), false)),
     );
     methods.insert(
         "string".into(),
@@ -43,24 +66,86 @@ pub fn native_rand() -> Value {
         "int".into(),
         Method::Native(NativeMethod::new(Rc::new(FnRandInt), false)),
     );
+    methods.insert(
+        "shuffle".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnRandShuffle), false)),
+    );
+    methods.insert(
+        "sample".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnRandSample), false)),
+    );
+    methods.insert(
+        "seed".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnRandSeed), false)),
+    );
 
     Value::Obj(Rc::new(Object::new("Rand".into(), methods)))
 }
 
 // rand() -> Num (0..1)
 native_fn!(FnRandNum, "num", 0, |_evaluator, _args, _cursor| {
-    let mut rng = rand::rng();
-    Ok(Value::Num(OrderedFloat(rng.random())))
+    Ok(Value::Num(OrderedFloat(with_rng(|rng| rng.random()))))
 });
 
-// rand_bool() -> Bool
-native_fn!(FnRandBool, "bool", 0, |_evaluator, _args, _cursor| {
-    let mut rng = rand::rng();
-    Ok(Value::Bool(rng.random()))
+// rand_bool(p?: Num) -> Bool: true with probability p, 0.5 if it's omitted
+native_fn!(FnRandBool, "bool", 0..=1, |_evaluator, args, cursor| {
+    let Some(p) = args.first() else {
+        return Ok(Value::Bool(with_rng(|rng| rng.random())));
+    };
+    let p = p.check_num(cursor, Some("probability".into()))?;
+    if !(0.0..=1.0).contains(&p) {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!("probability must be between 0 and 1, found {}", p),
+            cursor,
+        ));
+    }
+    Ok(Value::Bool(with_rng(|rng| rng.random_bool(p))))
 });
 
-// rand_list(list: List) -> Value
-native_fn!(FnRandList, "list", 1, |_evaluator, args, cursor| {
+// rand_weighted(choices: List, weights: List) -> Value: picks an element of choices, each one
+// with a probability proportional to its weight
+native_fn!(FnRandWeighted, "weighted", 2, |_evaluator, args, cursor| {
+    let rc_choices = args[0].check_list(cursor, Some("choices".into()))?;
+    let rc_weights = args[1].check_list(cursor, Some("weights".into()))?;
+    let choices = rc_choices.borrow();
+    let weights = rc_weights
+        .borrow()
+        .iter()
+        .map(|w| w.check_num(cursor, Some("weight".into())))
+        .collect::<EvalResult<Vec<f64>>>()?;
+
+    if choices.len() != weights.len() {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!(
+                "expected a weight for each of the {} choices, found {} weights",
+                choices.len(),
+                weights.len()
+            ),
+            cursor,
+        ));
+    }
+    if let Some(w) = weights.iter().find(|w| !(**w >= 0.0 && w.is_finite())) {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!("weights must be finite and non-negative, found {}", w),
+            cursor,
+        ));
+    }
+    let dist = WeightedIndex::new(&weights).map_err(|_| {
+        RuntimeEvent::error(
+            ErrKind::Value,
+            "cannot choose from choices whose weights are all 0".into(),
+            cursor,
+        )
+    })?;
+    let idx = with_rng(|rng| dist.sample(rng));
+    Ok(choices[idx].clone())
+});
+
+// rand_choice(list: List) -> Value
+native_fn!(FnRandChoice, "choice", 1, |_evaluator, args, cursor| {
     let rc_list = args[0].check_list(cursor, Some("list argument".into()))?;
     let list = rc_list.borrow();
     if list.is_empty() {
@@ -70,8 +155,7 @@ native_fn!(FnRandList, "list", 1, |_evaluator, args, cursor| {
             cursor,
         ));
     }
-    let mut rng = rand::rng();
-    let idx = rng.random_range(0..list.len());
+    let idx = with_rng(|rng| rng.random_range(0..list.len()));
     Ok(list[idx].clone())
 });
 
@@ -93,13 +177,14 @@ native_fn!(FnRandString, "string", 1, |_evaluator, args, cursor| {
         ));
     }
     let len = len_num as usize;
-    let mut rng = rand::rng();
-    let result: String = (0..len)
-        .map(|_| {
-            let idx = rng.random_range(0..RAND_STRING_CHARSET.len());
-            RAND_STRING_CHARSET[idx] as char
-        })
-        .collect();
+    let result: String = with_rng(|rng| {
+        (0..len)
+            .map(|_| {
+                let idx = rng.random_range(0..RAND_STRING_CHARSET.len());
+                RAND_STRING_CHARSET[idx] as char
+            })
+            .collect()
+    });
     Ok(Value::Str(Rc::new(RefCell::new(result))))
 });
 
@@ -107,15 +192,18 @@ native_fn!(FnRandString, "string", 1, |_evaluator, args, cursor| {
 native_fn!(FnRandRange, "range", 2, |_evaluator, args, cursor| {
     let min = args[0].check_num(cursor, Some("min value".into()))?;
     let max = args[1].check_num(cursor, Some("max value".into()))?;
-    if max <= min {
+    if max < min {
         return Err(RuntimeEvent::error(
             ErrKind::Value,
-            "max must be greater than min when calling Rand.range".into(),
+            "max must be greater than or equal to min when calling Rand.range".into(),
             cursor,
         ));
     }
-    let mut rng = rand::rng();
-    let value = rng.random_range(min..max);
+    let value = if max == min {
+        min
+    } else {
+        with_rng(|rng| rng.random_range(min..max))
+    };
     Ok(Value::Num(OrderedFloat(value)))
 });
 
@@ -139,11 +227,199 @@ native_fn!(FnRandInt, "int", 2, |_evaluator, args, cursor| {
             cursor,
         ));
     }
-    let mut rng = rand::rng();
     let value = if max == min {
         min
     } else {
-        rng.random_range(min..=max)
+        with_rng(|rng| rng.random_range(min..=max))
     };
     Ok(Value::Num(OrderedFloat(value as f64)))
 });
+
+// rand_shuffle(list: List) -> Null: shuffles the list in place
+native_fn!(FnRandShuffle, "shuffle", 1, |_evaluator, args, cursor| {
+    let rc_list = args[0].check_list(cursor, Some("list argument".into()))?;
+    with_rng(|rng| rc_list.borrow_mut().shuffle(rng));
+    Ok(Value::Null)
+});
+
+// rand_sample(list: List, n: Num) -> List: n distinct elements of the list in random order
+native_fn!(FnRandSample, "sample", 2, |_evaluator, args, cursor| {
+    let rc_list = args[0].check_list(cursor, Some("list argument".into()))?;
+    let n = args[1].check_num(cursor, Some("sample count".into()))?;
+    let list = rc_list.borrow();
+    if n < 0.0 || n.fract() != 0.0 || n > list.len() as f64 {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!(
+                "sample count must be an integer between 0 and the list length ({}), found {}",
+                list.len(),
+                n
+            ),
+            cursor,
+        ));
+    }
+    let indices = with_rng(|rng| index::sample(rng, list.len(), n as usize));
+    let sampled = indices.iter().map(|i| list[i].clone()).collect();
+    Ok(Value::List(Rc::new(RefCell::new(sampled))))
+});
+
+// rand_seed(seed: Num) -> Null: reseeds the RNG so the following values are reproducible
+native_fn!(FnRandSeed, "seed", 1, |_evaluator, args, cursor| {
+    let seed = args[0].check_num(cursor, Some("seed".into()))?;
+    if !seed.is_finite() {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!("Rand.seed expects a finite seed, found {}", seed),
+            cursor,
+        ));
+    }
+    if seed.fract().abs() > f64::EPSILON {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            "Rand.seed expects an integer seed".into(),
+            cursor,
+        ));
+    }
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed as i64 as u64));
+    Ok(Value::Null)
+});
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::{
+        runtime_err::ErrKind,
+        tests::{assert_err_kind, global, run},
+    };
+
+    #[test]
+    fn seeded_sequences_repeat() {
+        let src = "
+fn sample() do
+    return [Rand.num(), Rand.int(0, 100), Rand.range(-1, 1), Rand.choice([1, 2, 3]), Rand.string(8)]
+end
+Rand.seed(42)
+var a = sample()
+Rand.seed(42)
+var b = sample()
+Rand.seed(7)
+var c = sample()
+";
+        let a = global(src, "a").to_string();
+        assert_eq!(a, global(src, "b").to_string());
+        assert_ne!(a, global(src, "c").to_string());
+    }
+
+    #[test]
+    fn shuffle_and_sample() {
+        let src = "
+var a = range(0, 20)
+Rand.seed(3)
+Rand.shuffle(a)
+var b = range(0, 20)
+Rand.seed(3)
+Rand.shuffle(b)
+var sorted = a.slice(0, 20)
+sorted.sort()
+";
+        let a = global(src, "a").to_string();
+        assert_eq!(a, global(src, "b").to_string());
+        assert_ne!(a, global("var x = range(0, 20)", "x").to_string());
+        assert_eq!(
+            global(src, "sorted").to_string(),
+            global("var x = range(0, 20)", "x").to_string()
+        );
+
+        // sampled elements are the same values, not copies
+        let src = "
+var inner = [1]
+var s = Rand.sample([inner, [2], [3]], 3)
+for item in s do
+    item.push(0)
+end
+var all = Rand.sample([1, 2, 3], 3)
+all.sort()
+";
+        assert_eq!(global(src, "inner").to_string(), "[1, 0]");
+        assert_eq!(global(src, "all").to_string(), "[1, 2, 3]");
+        assert_eq!(global("var x = Rand.sample([1], 0)", "x").to_string(), "[]");
+    }
+
+    #[test]
+    fn int_is_inclusive_and_integral() {
+        let src = "
+var ok = true
+for i in 0..200 do
+    var n = Rand.int(1, 3)
+    if n < 1 or n > 3 or n != n.floor() do
+        ok = false
+    end
+end
+";
+        assert_eq!(global(src, "ok").to_string(), "true");
+        assert_eq!(global("var x = Rand.int(5, 5)", "x").to_string(), "5");
+    }
+
+    #[test]
+    fn bool_probability() {
+        let src = "
+var never = false
+var always = true
+for i in 0..100 do
+    never = never or Rand.bool(0)
+    always = always and Rand.bool(1)
+end
+";
+        assert_eq!(global(src, "never").to_string(), "false");
+        assert_eq!(global(src, "always").to_string(), "true");
+        assert_eq!(global("var x = type(Rand.bool())", "x").to_string(), "Bool");
+    }
+
+    #[test]
+    fn weighted_choice() {
+        let src = "
+fn picks() do
+    var out = []
+    for i in 0..20 do
+        out.push(Rand.weighted([\"a\", \"b\", \"c\"], [1, 0, 3]))
+    end
+    return out
+end
+Rand.seed(11)
+var a = picks()
+Rand.seed(11)
+var b = picks()
+var never_b = not a.contains(\"b\")
+";
+        let a = global(src, "a").to_string();
+        assert_eq!(a, global(src, "b").to_string());
+        assert_eq!(global(src, "never_b").to_string(), "true");
+        assert_eq!(
+            global("var x = Rand.weighted([1, 2], [0, 5])", "x").to_string(),
+            "2"
+        );
+    }
+
+    #[test]
+    fn invalid_bounds_are_value_errs() {
+        for src in [
+            "Rand.int(3, 1)",
+            "Rand.range(3, 1)",
+            "Rand.choice([])",
+            "Rand.sample([1, 2], 3)",
+            "Rand.sample([1, 2], 1.5)",
+            "Rand.bool(2)",
+            "Rand.weighted([1, 2], [1])",
+            "Rand.weighted([1, 2], [1, -1])",
+            "Rand.weighted([1, 2], [0, 0])",
+            "Rand.weighted([], [])",
+            "Rand.seed(1.5)",
+            "Rand.seed(0 / 0)",
+            "Rand.seed(1 / 0)",
+            "Rand.seed(-1 / 0)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+}
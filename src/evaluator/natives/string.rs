@@ -0,0 +1,236 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    evaluator::{
+        Callable, EvalResult, Evaluator,
+        natives::math::to_places,
+        object::{Method, NativeMethod, Object},
+        prototype::{Prototype, ValuePrototypes},
+        runtime_err::{ErrKind, RuntimeEvent},
+        value::Value,
+    },
+    lexer::cursor::Cursor,
+    native_fn,
+};
+
+/// Most decimal places Str.format_num accepts, an f64 has no meaningful digits past this
+const MAX_DECIMALS: f64 = 100.;
+
+/// Str methods that can also be called as `Str.name(s, ...)`
+const STR_FUNCTIONS: [&str; 14] = [
+    "upper",
+    "lower",
+    "trim",
+    "split",
+    "replace",
+    "contains",
+    "starts_with",
+    "ends_with",
+    "find",
+    "substr",
+    "chars",
+    "bytes",
+    "pad_left",
+    "pad_right",
+];
+
+pub fn native_str() -> Value {
+    let mut methods: HashMap<String, Method> = HashMap::new();
+
+    methods.insert(
+        "format_num".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnStrFormatNum), false)),
+    );
+
+    let value_proto = Rc::new(ValuePrototypes::value_proto());
+    let str_proto = ValuePrototypes::str_proto(&value_proto);
+    for name in STR_FUNCTIONS {
+        methods.insert(name.into(), proto_function(&str_proto, name));
+    }
+    // join(list, sep) -> Str: the inverse of split, the List's join method
+    let list_proto = ValuePrototypes::list_proto(&value_proto);
+    methods.insert("join".into(), proto_function(&list_proto, "join"));
+
+    Value::Obj(Rc::new(Object::new("Str".into(), methods)))
+}
+
+/// The method `name` of `proto` as a function taking the receiver as its first argument
+fn proto_function(proto: &Prototype, name: &str) -> Method {
+    let method = proto
+        .get_method(name.into())
+        .expect("Str functions should be prototype methods");
+    let function = ProtoFunction {
+        method,
+        recv_type: proto.name.clone(),
+    };
+    Method::Native(NativeMethod::new(Rc::new(function), false))
+}
+
+/// Calls a prototype method with its first argument as the receiver, so `Str.upper(s)` shares
+/// its implementation with `s.upper()`
+#[derive(Debug)]
+struct ProtoFunction {
+    method: Rc<dyn Callable>,
+    /// Type the receiver needs to be, the method itself assumes it's right
+    recv_type: String,
+}
+
+impl Callable for ProtoFunction {
+    fn name(&self) -> &str {
+        self.method.name()
+    }
+
+    fn arity(&self) -> usize {
+        self.method.arity() + 1
+    }
+
+    fn min_arity(&self) -> usize {
+        self.method.min_arity() + 1
+    }
+
+    fn call(
+        &self,
+        evaluator: &mut Evaluator,
+        args: Vec<Value>,
+        cursor: Cursor,
+    ) -> EvalResult<Value> {
+        if args[0].get_type() != self.recv_type {
+            return Err(RuntimeEvent::error(
+                ErrKind::Type,
+                format!("expected {}, found {}", self.recv_type, args[0].get_type()),
+                cursor,
+            ));
+        }
+        self.method.call(evaluator, args, cursor)
+    }
+}
+
+// format_num(n: Num, decimals: Num) -> Str: n with exactly `decimals` digits after the decimal
+// point, rounded the same way as Math.round_to (halfway values round away from 0)
+native_fn!(
+    FnStrFormatNum,
+    "str_format_num",
+    2,
+    |_evaluator, args, cursor| {
+        let n = args[0].check_num(cursor, Some("number".into()))?;
+        let decimals = args[1].check_num(cursor, Some("decimal places".into()))?;
+        if !(0. ..=MAX_DECIMALS).contains(&decimals) || decimals.fract() != 0. {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!(
+                    "decimal places must be a whole number between 0 and {}, found {}",
+                    MAX_DECIMALS, decimals
+                ),
+                cursor,
+            ));
+        }
+        let rounded = to_places(n, decimals, f64::round, "Str.format_num", cursor)?;
+        Ok(Value::Str(Rc::new(RefCell::new(format!(
+            "{:.*}",
+            decimals as usize, rounded
+        )))))
+    }
+);
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::{
+        runtime_err::ErrKind,
+        tests::{assert_err_kind, eval, run},
+    };
+
+    #[test]
+    fn case_trim_and_split() {
+        assert_eq!(eval("Str.upper(\"abc\")"), "ABC");
+        assert_eq!(eval("Str.lower(\"AbC\")"), "abc");
+        assert_eq!(eval("Str.trim(\"  a b \")"), "a b");
+        assert_eq!(eval("Str.split(\"a,b,c\", \",\")"), "[\"a\", \"b\", \"c\"]");
+        assert_eq!(eval("Str.split(\"a,b,c\", \",\").len()"), "3");
+        assert_eq!(eval("Str.replace(\"a-b-c\", \"-\", \"+\")"), "a+b+c");
+        assert_eq!(eval("Str.contains(\"hello\", \"ell\")"), "true");
+
+        for src in [
+            "Str.upper(1)",
+            "Str.split([\"a\"], \",\")",
+            "Str.split(\"a\", 1)",
+            "Str.contains(\"a\", true)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn search_and_substr() {
+        assert_eq!(eval("Str.starts_with(\"hello\", \"he\")"), "true");
+        assert_eq!(eval("Str.ends_with(\"hello\", \"he\")"), "false");
+        assert_eq!(eval("Str.find(\"héllo\", \"l\")"), "2");
+        assert_eq!(eval("Str.find(\"hello\", \"z\")"), "-1");
+        assert_eq!(eval("Str.substr(\"hello\", 1, 3)"), "ell");
+        // clamped to the end of the Str
+        assert_eq!(eval("Str.substr(\"hello\", 3, 10)"), "lo");
+
+        for src in ["Str.find(1, \"a\")", "Str.starts_with(\"a\", 1)"] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn chars_and_bytes() {
+        assert_eq!(eval("Str.chars(\"ab\")"), "[\"a\", \"b\"]");
+        assert_eq!(eval("Str.chars(\"ab\") == [\"a\", \"b\"]"), "true");
+        assert_eq!(eval("Str.bytes(\"A\")"), "[65]");
+        assert_eq!(eval("Str.bytes(\"é\")"), "[195, 169]");
+
+        let (result, _) = run("Str.chars([\"a\"])");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(eval("Str.join([\"a\", \"b\", \"c\"], \"-\")"), "a-b-c");
+        assert_eq!(eval("Str.join([1, 2.5, 3], \", \")"), "1, 2.5, 3");
+        assert_eq!(eval("Str.join([], \"-\")"), "");
+
+        for src in ["Str.join(\"abc\", \"-\")", "Str.join([1], 2)"] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn pad() {
+        assert_eq!(eval("Str.pad_left(\"7\", 3, \"0\")"), "007");
+        assert_eq!(eval("Str.pad_right(\"ab\", 4, \".\")"), "ab..");
+        assert_eq!(eval("Str.pad_left(\"ab\", 4)"), "  ab");
+        // a smaller width leaves the Str as is
+        assert_eq!(eval("Str.pad_left(\"hello\", 2, \"0\")"), "hello");
+
+        let (result, _) = run("Str.pad_left(7, 3, \"0\")");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn format_num() {
+        assert_eq!(eval("Str.format_num(3.14159, 2)"), "3.14");
+        assert_eq!(eval("Str.format_num(2, 3)"), "2.000");
+        // same rounding as Math.round_to
+        assert_eq!(eval("Str.format_num(2.5, 0)"), "3");
+        assert_eq!(eval("Str.format_num(-2.5, 0)"), "-3");
+        assert_eq!(eval("Str.format_num(42, 0).pad_left(5, \"0\")"), "00042");
+
+        for src in [
+            "Str.format_num(1, -1)",
+            "Str.format_num(1, 1.5)",
+            "Str.format_num(1, 100000000000)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+
+        let (result, _) = run("Str.format_num(\"1\", 2)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+}
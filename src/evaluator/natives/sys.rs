@@ -1,20 +1,25 @@
 use std::{
     cell::RefCell,
     collections::HashMap,
+    io::{self, Write},
+    process::Command,
     rc::Rc,
     thread,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+use indexmap::IndexMap;
 use ordered_float::OrderedFloat;
 
 use crate::{
     evaluator::{
         Callable, EvalResult, Evaluator,
+        natives::tui,
         object::{Method, NativeMethod, Object},
         runtime_err::{ErrKind, RuntimeEvent},
-        value::Value,
+        value::{Value, ValueKey},
     },
+    lexer::cursor::Cursor,
     native_fn,
 };
 
@@ -33,6 +38,10 @@ pub fn native_sys() -> Value {
         "env".into(),
         Method::Native(NativeMethod::new(Rc::new(FnSysEnv), false)),
     );
+    methods.insert(
+        "set_env".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysSetEnv), false)),
+    );
     methods.insert(
         "args".into(),
         Method::Native(NativeMethod::new(Rc::new(FnSysArgs), false)),
@@ -41,6 +50,30 @@ pub fn native_sys() -> Value {
         "cwd".into(),
         Method::Native(NativeMethod::new(Rc::new(FnSysCwd), false)),
     );
+    methods.insert(
+        "platform".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysPlatform), false)),
+    );
+    methods.insert(
+        "arch".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysArch), false)),
+    );
+    methods.insert(
+        "now".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysNow), false)),
+    );
+    methods.insert(
+        "input_available".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysInputAvailable), false)),
+    );
+    methods.insert(
+        "run".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysRun), false)),
+    );
+    methods.insert(
+        "exit".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnSysExit), false)),
+    );
 
     Value::Obj(Rc::new(Object::new("Sys".into(), methods)))
 }
@@ -53,12 +86,23 @@ native_fn!(FnSysClock, "sys_clock", 0, |_evaluator, _args, _cursor| {
     Ok(Value::Num(OrderedFloat(from_epoch.as_millis() as f64)))
 });
 
-// sleep(ms: Num)
-native_fn!(FnSysSleep, "sys_sleep", 1, |_evaluator, args, _cursor| {
-    if let Value::Num(millis) = args[0] {
-        thread::sleep(Duration::from_millis(millis.0 as u64));
-    }
-    Ok(Value::Null)
+// sleep(ms: Num) -> Num: fractional milliseconds are allowed and negative durations don't
+// sleep at all, returns the milliseconds that actually passed
+native_fn!(FnSysSleep, "sys_sleep", 1, |_evaluator, args, cursor| {
+    let millis = args[0].check_num(cursor, Some("sleep duration".into()))?;
+    let duration = Duration::try_from_secs_f64(millis.max(0.0) / 1000.0).map_err(|_| {
+        RuntimeEvent::error(
+            ErrKind::Value,
+            format!("invalid sleep duration {}", millis),
+            cursor,
+        )
+    })?;
+
+    let start = Instant::now();
+    thread::sleep(duration);
+    Ok(Value::Num(OrderedFloat(
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
 });
 
 // env(name: Str) -> Str | Null
@@ -71,14 +115,138 @@ native_fn!(FnSysEnv, "sys_env", 1, |_evaluator, args, cursor| {
     }
 });
 
-// args() -> List<Str>
-native_fn!(FnSysArgs, "sys_args", 0, |_evaluator, _args, _cursor| {
-    let values = std::env::args()
-        .map(|arg| Value::Str(Rc::new(RefCell::new(arg))))
+// set_env(name: Str, value: Str): sets an environment variable for the current process
+native_fn!(FnSysSetEnv, "sys_set_env", 2, |_evaluator, args, cursor| {
+    let name = args[0].check_str(cursor, Some("environment variable name".into()))?;
+    let value = args[1].check_str(cursor, Some("environment variable value".into()))?;
+    if name.borrow().is_empty() || name.borrow().contains(['=', '\0']) {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!("invalid environment variable name '{}'", name.borrow()),
+            cursor,
+        ));
+    }
+    if value.borrow().contains('\0') {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            "environment variable value can't contain a NUL character".into(),
+            cursor,
+        ));
+    }
+    // SAFETY: the interpreter runs programs on a single thread
+    unsafe {
+        std::env::set_var(name.borrow().as_str(), value.borrow().as_str());
+    }
+    Ok(Value::Null)
+});
+
+// run(cmd: Str, args?: List<Str>) -> Dict: runs a program directly (not through a shell) and
+// waits for it, returns {"stdout": Str, "stderr": Str, "code": Num | Null}
+native_fn!(FnSysRun, "sys_run", 1..=2, |_evaluator, args, cursor| {
+    let cmd = args[0].check_str(cursor, Some("command".into()))?;
+    let mut command = Command::new(cmd.borrow().as_str());
+    if let Some(cmd_args) = args.get(1) {
+        for arg in cmd_args
+            .check_list(cursor, Some("command arguments".into()))?
+            .borrow()
+            .iter()
+        {
+            command.arg(
+                arg.check_str(cursor, Some("command argument".into()))?
+                    .borrow()
+                    .as_str(),
+            );
+        }
+    }
+
+    let output = command.output().map_err(|err| {
+        RuntimeEvent::error(
+            ErrKind::IO,
+            format!("failed to run '{}': {}", cmd.borrow(), err),
+            cursor,
+        )
+    })?;
+
+    let mut result = IndexMap::new();
+    result.insert(
+        ValueKey::Str("stdout".into()),
+        Value::str(&String::from_utf8_lossy(&output.stdout)),
+    );
+    result.insert(
+        ValueKey::Str("stderr".into()),
+        Value::str(&String::from_utf8_lossy(&output.stderr)),
+    );
+    // there's no exit code when the process was killed by a signal
+    result.insert(
+        ValueKey::Str("code".into()),
+        match output.status.code() {
+            Some(code) => Value::Num(OrderedFloat(code as f64)),
+            None => Value::Null,
+        },
+    );
+    Ok(Value::Dict(Rc::new(RefCell::new(result))))
+});
+
+// input_available() -> Bool: whether there's input waiting on stdin, never blocks itself. Lines
+// read() already pulled into its buffer aren't seen, so this can be false while read() would
+// still return without waiting
+native_fn!(
+    FnSysInputAvailable,
+    "sys_input_available",
+    0,
+    |_evaluator, _args, _cursor| { Ok(Value::Bool(stdin_ready())) }
+);
+
+/// Poll stdin without waiting, a closed stdin counts as ready since reading it returns at once.
+/// Only the file descriptor is checked, `io::stdin()` can't be asked about its own buffer
+/// without blocking when it's empty
+#[cfg(unix)]
+fn stdin_ready() -> bool {
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // SAFETY: fd is a single valid pollfd and a 0 timeout makes poll return immediately
+    let ready = unsafe { libc::poll(&mut fd, 1, 0) };
+    ready > 0 && fd.revents & (libc::POLLIN | libc::POLLHUP) != 0
+}
+
+/// Stdin can't be polled on this platform, input is never reported as available
+#[cfg(not(unix))]
+fn stdin_ready() -> bool {
+    false
+}
+
+// args() -> List<Str>: arguments passed after the program file
+native_fn!(FnSysArgs, "sys_args", 0, |evaluator, _args, _cursor| {
+    let values = evaluator
+        .args
+        .iter()
+        .map(|arg| Value::Str(Rc::new(RefCell::new(arg.clone()))))
         .collect::<Vec<Value>>();
     Ok(Value::List(Rc::new(RefCell::new(values))))
 });
 
+// platform() -> Str: the OS name, eg. "linux", "macos" or "windows"
+native_fn!(
+    FnSysPlatform,
+    "sys_platform",
+    0,
+    |_evaluator, _args, _cursor| {
+        Ok(Value::Str(Rc::new(RefCell::new(
+            std::env::consts::OS.to_string(),
+        ))))
+    }
+);
+
+// arch() -> Str: the CPU architecture, eg. "x86_64" or "aarch64"
+native_fn!(FnSysArch, "sys_arch", 0, |_evaluator, _args, _cursor| {
+    Ok(Value::Str(Rc::new(RefCell::new(
+        std::env::consts::ARCH.to_string(),
+    ))))
+});
+
 // cwd() -> Str
 native_fn!(FnSysCwd, "sys_cwd", 0, |_evaluator, _args, cursor| {
     let cwd = std::env::current_dir().map_err(|err| {
@@ -92,3 +260,287 @@ native_fn!(FnSysCwd, "sys_cwd", 0, |_evaluator, _args, cursor| {
         cwd.to_string_lossy().to_string(),
     ))))
 });
+
+const DEFAULT_TIME_FMT: &str = "%Y-%m-%d %H:%M:%S";
+
+// now(fmt?: Str) -> Str: local time formatted with strftime-style specifiers, as
+// "YYYY-MM-DD HH:MM:SS" when fmt is left out
+native_fn!(FnSysNow, "sys_now", 0..=1, |_evaluator, args, cursor| {
+    let text = match args.first() {
+        Some(fmt) => {
+            let fmt = fmt.check_str(cursor, Some("time format".into()))?;
+            format_time(local_now(), fmt.borrow().as_str(), cursor)?
+        }
+        None => format_time(local_now(), DEFAULT_TIME_FMT, cursor)?,
+    };
+    Ok(Value::Str(Rc::new(RefCell::new(text))))
+});
+
+/// Broken down wall-clock time
+#[derive(Debug, PartialEq)]
+struct DateTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    min: u32,
+    sec: u32,
+}
+
+impl DateTime {
+    /// Convert seconds since the unix epoch to a date, using the days-to-civil algorithm
+    /// from http://howardhinnant.github.io/date_algorithms.html
+    fn from_unix(secs: i64) -> Self {
+        let days = secs.div_euclid(86400);
+        let rem = secs.rem_euclid(86400);
+
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (rem / 3600) as u32,
+            min: (rem % 3600 / 60) as u32,
+            sec: (rem % 60) as u32,
+        }
+    }
+}
+
+fn local_now() -> DateTime {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should go forward")
+        .as_secs() as i64;
+    DateTime::from_unix(secs + local_offset(secs))
+}
+
+/// Offset of the local timezone from UTC in seconds
+#[cfg(unix)]
+fn local_offset(secs: i64) -> i64 {
+    let time = secs as libc::time_t;
+    // SAFETY: tm is plain data and localtime_r only writes into the tm we pass it
+    unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&time, &mut tm).is_null() {
+            return 0;
+        }
+        tm.tm_gmtoff as i64
+    }
+}
+
+/// Timezones aren't supported on this platform, fall back to UTC
+#[cfg(not(unix))]
+fn local_offset(_secs: i64) -> i64 {
+    0
+}
+
+/// Format `time` with the %Y, %m, %d, %H, %M, %S and %% specifiers
+fn format_time(time: DateTime, fmt: &str, cursor: Cursor) -> EvalResult<String> {
+    let mut out = String::new();
+    let mut chars = fmt.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(&format!("{:04}", time.year)),
+            Some('m') => out.push_str(&format!("{:02}", time.month)),
+            Some('d') => out.push_str(&format!("{:02}", time.day)),
+            Some('H') => out.push_str(&format!("{:02}", time.hour)),
+            Some('M') => out.push_str(&format!("{:02}", time.min)),
+            Some('S') => out.push_str(&format!("{:02}", time.sec)),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                return Err(RuntimeEvent::error(
+                    ErrKind::Value,
+                    format!("unknown time format specifier '%{}'", other),
+                    cursor,
+                ));
+            }
+            None => {
+                return Err(RuntimeEvent::error(
+                    ErrKind::Value,
+                    "time format can't end with a lone '%'".into(),
+                    cursor,
+                ));
+            }
+        }
+    }
+    Ok(out)
+}
+
+// exit(code: Num): flushes stdout, restores the terminal and exits the process
+native_fn!(FnSysExit, "sys_exit", 1, |_evaluator, args, cursor| {
+    let code = args[0].check_num(cursor, Some("exit code".into()))?;
+    let _ = io::stdout().flush();
+    tui::restore_terminal();
+    std::process::exit(code as i32);
+});
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::{DateTime, format_time};
+    use crate::{
+        evaluator::{
+            runtime_err::ErrKind,
+            tests::{assert_err_kind, global, run, run_with_args},
+            value::Value,
+        },
+        lexer::cursor::Cursor,
+    };
+
+    #[cfg(unix)]
+    #[test]
+    fn run_captures_output() {
+        let src = "
+var res = Sys.run(\"echo\", [\"hello\"])
+var out = res[\"stdout\"]
+var code = res[\"code\"]
+var failed = Sys.run(\"sh\", [\"-c\", \"echo oops >&2; exit 3\"])
+var err = failed[\"stderr\"]
+var err_code = failed[\"code\"]
+";
+        assert_eq!(global(src, "out").to_string(), "hello\n");
+        assert_eq!(global(src, "code").to_string(), "0");
+        assert_eq!(global(src, "err").to_string(), "oops\n");
+        assert_eq!(global(src, "err_code").to_string(), "3");
+
+        let (result, _) = run("Sys.run(\"quetite-no-such-command\")");
+        assert_err_kind(result, ErrKind::IO);
+        let (result, _) = run("Sys.run(\"echo\", [1])");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn input_available_is_bool() {
+        let start = std::time::Instant::now();
+        let available = global("var a = Sys.input_available()", "a");
+        assert!(matches!(available, Value::Bool(_)));
+        assert!(start.elapsed().as_secs() < 1);
+    }
+
+    #[test]
+    fn platform_and_arch() {
+        let platform = global("var p = Sys.platform()", "p").to_string();
+        assert!(!platform.is_empty());
+        assert_eq!(platform, std::env::consts::OS);
+
+        let arch = global("var a = Sys.arch()", "a").to_string();
+        assert!(!arch.is_empty());
+        assert_eq!(arch, std::env::consts::ARCH);
+    }
+
+    #[test]
+    fn sleep_returns_elapsed() {
+        for millis in ["0", "-5", "0.5"] {
+            let slept = global(&format!("var slept = Sys.sleep({millis})"), "slept");
+            let Value::Num(slept) = slept else {
+                panic!("expected a Num");
+            };
+            assert!(slept.0 >= 0.0 && slept.0 < 1000.0);
+        }
+
+        let (result, _) = run("Sys.sleep(1 / 0)");
+        assert_err_kind(result, ErrKind::Value);
+    }
+
+    #[test]
+    fn exit_requires_num() {
+        let (result, _) = run("Sys.exit(\"1\")");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn args_are_exposed() {
+        let (result, globals) = run_with_args(
+            "var args = Sys.args()",
+            vec!["foo".to_string(), "bar".to_string()],
+        );
+        assert!(result.is_ok());
+        let args = globals.borrow().get("args", Cursor::new()).unwrap();
+        assert_eq!(args.to_string(), "[\"foo\", \"bar\"]");
+    }
+
+    #[test]
+    fn set_then_read_env() {
+        let src = "
+Sys.set_env(\"QUETITE_TEST_SET_ENV\", \"hello\")
+var val = Sys.env(\"QUETITE_TEST_SET_ENV\")
+var unset = Sys.env(\"QUETITE_TEST_SURELY_UNSET_VARIABLE\")
+";
+        assert_eq!(global(src, "val").to_string(), "hello");
+        assert_eq!(global(src, "unset").to_string(), "null");
+    }
+
+    #[test]
+    fn env_requires_str() {
+        for src in [
+            "Sys.env(1)",
+            "Sys.set_env(\"A\", 1)",
+            "Sys.set_env(true, \"a\")",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn unix_to_date() {
+        assert_eq!(
+            DateTime::from_unix(0),
+            DateTime {
+                year: 1970,
+                month: 1,
+                day: 1,
+                hour: 0,
+                min: 0,
+                sec: 0
+            }
+        );
+        let time = DateTime::from_unix(1704207845);
+        assert_eq!(
+            format_time(time, "%Y-%m-%d %H:%M:%S %%", Cursor::new()).unwrap(),
+            "2024-01-02 15:04:05 %"
+        );
+        // leap day
+        let time = DateTime::from_unix(1709210096);
+        assert_eq!(
+            format_time(time, "%d/%m/%Y", Cursor::new()).unwrap(),
+            "29/02/2024"
+        );
+    }
+
+    #[test]
+    fn now_default_format() {
+        let now = global("var now = Sys.now()", "now").to_string();
+        let digit_at = [0, 1, 2, 3, 5, 6, 8, 9, 11, 12, 14, 15, 17, 18];
+        assert_eq!(now.len(), 19, "{now}");
+        for (i, ch) in now.chars().enumerate() {
+            match i {
+                4 | 7 => assert_eq!(ch, '-', "{now}"),
+                10 => assert_eq!(ch, ' ', "{now}"),
+                13 | 16 => assert_eq!(ch, ':', "{now}"),
+                _ => assert!(digit_at.contains(&i) && ch.is_ascii_digit(), "{now}"),
+            }
+        }
+
+        let year = global("var year = Sys.now(\"%Y\")", "year").to_string();
+        assert_eq!(year, now[..4]);
+
+        let (result, _) = run("Sys.now(\"%Q\")");
+        assert_err_kind(result, ErrKind::Value);
+        let (result, _) = run("Sys.now(1)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+}
@@ -8,20 +8,26 @@ use std::{
 
 use crate::{
     evaluator::{
-        Callable, EvalResult, Evaluator,
+        Callable, ErrKind, EvalResult, Evaluator, RuntimeEvent,
+        natives::tui::try_parse_color,
         object::{Method, NativeMethod, Object},
         value::Value,
     },
+    lexer::cursor::Cursor,
     native_fn, native_fn_with_data, native_fn_with_val,
 };
 
 use crossterm::{
     cursor::MoveTo,
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
-    terminal::{Clear, ClearType, SetTitle, disable_raw_mode, enable_raw_mode},
+    style::{Color as TermColor, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{
+        Clear, ClearType, SetTitle, disable_raw_mode, enable_raw_mode, is_raw_mode_enabled,
+    },
 };
 use ordered_float::OrderedFloat;
+use ratatui::style::Color;
 
 pub fn native_term() -> Value {
     let mut methods: HashMap<String, Method> = HashMap::new();
@@ -34,6 +40,10 @@ pub fn native_term() -> Value {
         "get_input".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTermGetInput), false)),
     );
+    methods.insert(
+        "read_key".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTermReadKey), false)),
+    );
     methods.insert(
         "cursor_hide".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTermCursorHide), false)),
@@ -78,11 +88,19 @@ pub fn native_term() -> Value {
         "flush".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTermFlush), false)),
     );
+    methods.insert(
+        "set_color".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTermSetColor), false)),
+    );
+    methods.insert(
+        "reset_color".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTermResetColor), false)),
+    );
 
     Value::Obj(Rc::new(Object::new("Term".into(), methods)))
 }
 
-// Term.size() -> [width, height]: returns terminal dimensions
+// Term.size() -> [width, height]: returns terminal dimensions, also used as Tui.terminal_size()
 native_fn!(
     FnTermSize,
     "terminal_size",
@@ -104,69 +122,122 @@ native_fn!(
     |_evaluator, _args, _cursor| {
         if event::poll(Duration::from_millis(0))? {
             if let Event::Key(key_event) = event::read()? {
-                let key_str = match key_event.code {
-                    KeyCode::BackTab => "Tab".into(),
-                    _ => key_event.code.to_string(),
-                };
-
-                // Extract modifiers
-                let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
-                let shift = key_event.modifiers.contains(KeyModifiers::SHIFT)
-                    || matches!(key_event.code, KeyCode::BackTab);
-                let alt = key_event.modifiers.contains(KeyModifiers::ALT);
-
-                // Create key data
-                let key_data = Rc::new(RefCell::new(KeyInputData {
-                    key: key_str,
-                    ctrl,
-                    shift,
-                    alt,
-                }));
-
-                // Create methods
-                let mut methods: HashMap<String, Method> = HashMap::new();
-
-                methods.insert(
-                    "key".into(),
-                    Method::Native(NativeMethod::new(
-                        Rc::new(KeyInputKeyGetter {
-                            data: Rc::clone(&key_data),
-                        }),
-                        false,
-                    )),
-                );
-
-                methods.insert(
-                    "ctrl".into(),
-                    Method::Native(NativeMethod::new(
-                        Rc::new(KeyInputCtrlGetter { val: ctrl }),
-                        false,
-                    )),
-                );
-
-                methods.insert(
-                    "shift".into(),
-                    Method::Native(NativeMethod::new(
-                        Rc::new(KeyInputShiftGetter { val: shift }),
-                        false,
-                    )),
-                );
-
-                methods.insert(
-                    "alt".into(),
-                    Method::Native(NativeMethod::new(
-                        Rc::new(KeyInputAltGetter { val: alt }),
-                        false,
-                    )),
-                );
-
-                return Ok(Value::Obj(Rc::new(Object::new("KeyInput".into(), methods))));
+                return Ok(key_input(key_event));
             }
         }
         Ok(Value::Null)
     }
 );
 
+// Term.read_key() -> Str: waits for a single keypress and returns its name (eg. "a", "Up", "Enter")
+native_fn!(
+    FnTermReadKey,
+    "terminal_read_key",
+    0,
+    |_evaluator, _args, _cursor| {
+        let _guard = RawModeGuard::enable()?;
+        loop {
+            match event::read()? {
+                Event::Key(key_event) if key_event.kind != KeyEventKind::Release => {
+                    return Ok(Value::Str(Rc::new(RefCell::new(key_name(key_event)))));
+                }
+                _ => {}
+            }
+        }
+    }
+);
+
+/// Enables raw mode for as long as it's alive, the previous mode is restored when dropped so
+/// errors in between can't leave the terminal in raw mode
+struct RawModeGuard {
+    was_enabled: bool,
+}
+
+impl RawModeGuard {
+    fn enable() -> io::Result<Self> {
+        let was_enabled = is_raw_mode_enabled()?;
+        if !was_enabled {
+            enable_raw_mode()?;
+        }
+        Ok(Self { was_enabled })
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        if !self.was_enabled {
+            let _ = disable_raw_mode();
+        }
+    }
+}
+
+// Name of the pressed key, shift+tab is reported as "Tab" with shift held
+fn key_name(key_event: KeyEvent) -> String {
+    match key_event.code {
+        KeyCode::BackTab => "Tab".into(),
+        _ => key_event.code.to_string(),
+    }
+}
+
+// Build a KeyInput object with key(), ctrl(), shift() and alt() getters, also used by
+// Tui.poll_event()
+pub fn key_input(key_event: KeyEvent) -> Value {
+    let key_str = key_name(key_event);
+
+    // Extract modifiers
+    let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+    let shift = key_event.modifiers.contains(KeyModifiers::SHIFT)
+        || matches!(key_event.code, KeyCode::BackTab);
+    let alt = key_event.modifiers.contains(KeyModifiers::ALT);
+
+    // Create key data
+    let key_data = Rc::new(RefCell::new(KeyInputData {
+        key: key_str,
+        ctrl,
+        shift,
+        alt,
+    }));
+
+    // Create methods
+    let mut methods: HashMap<String, Method> = HashMap::new();
+
+    methods.insert(
+        "key".into(),
+        Method::Native(NativeMethod::new(
+            Rc::new(KeyInputKeyGetter {
+                data: Rc::clone(&key_data),
+            }),
+            false,
+        )),
+    );
+
+    methods.insert(
+        "ctrl".into(),
+        Method::Native(NativeMethod::new(
+            Rc::new(KeyInputCtrlGetter { val: ctrl }),
+            false,
+        )),
+    );
+
+    methods.insert(
+        "shift".into(),
+        Method::Native(NativeMethod::new(
+            Rc::new(KeyInputShiftGetter { val: shift }),
+            false,
+        )),
+    );
+
+    methods.insert(
+        "alt".into(),
+        Method::Native(NativeMethod::new(
+            Rc::new(KeyInputAltGetter { val: alt }),
+            false,
+        )),
+    );
+
+    Value::Obj(Rc::new(Object::new("KeyInput".into(), methods)))
+}
+
 // Key input data structure
 struct KeyInputData {
     key: String,
@@ -373,3 +444,128 @@ native_fn!(
         Ok(Value::Null)
     }
 );
+
+// Term.set_color(fg, bg): sets the colors of the text printed after it, Null keeps the current color
+native_fn!(
+    FnTermSetColor,
+    "terminal_set_color",
+    2,
+    |_evaluator, args, cursor| {
+        let mut stdout = io::stdout();
+        if let Some(fg) = term_color(&args[0], cursor)? {
+            execute!(stdout, SetForegroundColor(fg))?;
+        }
+        if let Some(bg) = term_color(&args[1], cursor)? {
+            execute!(stdout, SetBackgroundColor(bg))?;
+        }
+        stdout.flush()?;
+        Ok(Value::Null)
+    }
+);
+
+// Term.reset_color(): resets text colors to the terminal defaults
+native_fn!(
+    FnTermResetColor,
+    "terminal_reset_color",
+    0,
+    |_evaluator, _args, _cursor| {
+        execute!(io::stdout(), ResetColor)?;
+        io::stdout().flush()?;
+        Ok(Value::Null)
+    }
+);
+
+/// Parse a color argument with the same color syntax as Tui, Null means no change and "reset"
+/// means the terminal default color
+fn term_color(val: &Value, cursor: Cursor) -> EvalResult<Option<TermColor>> {
+    if let Value::Null = val {
+        return Ok(None);
+    }
+
+    let name = val
+        .check_str(cursor, Some("color".into()))?
+        .borrow()
+        .clone();
+    if name.trim().eq_ignore_ascii_case("reset") {
+        return Ok(Some(TermColor::Reset));
+    }
+
+    let color = try_parse_color(&name).ok_or_else(|| {
+        RuntimeEvent::error(ErrKind::Value, format!("unknown color '{}'", name), cursor)
+    })?;
+    Ok(Some(match color {
+        Color::Reset => TermColor::Reset,
+        Color::Black => TermColor::Black,
+        Color::Red => TermColor::DarkRed,
+        Color::Green => TermColor::DarkGreen,
+        Color::Yellow => TermColor::DarkYellow,
+        Color::Blue => TermColor::DarkBlue,
+        Color::Magenta => TermColor::DarkMagenta,
+        Color::Cyan => TermColor::DarkCyan,
+        Color::Gray => TermColor::Grey,
+        Color::DarkGray => TermColor::DarkGrey,
+        Color::LightRed => TermColor::Red,
+        Color::LightGreen => TermColor::Green,
+        Color::LightYellow => TermColor::Yellow,
+        Color::LightBlue => TermColor::Blue,
+        Color::LightMagenta => TermColor::Magenta,
+        Color::LightCyan => TermColor::Cyan,
+        Color::White => TermColor::White,
+        Color::Rgb(r, g, b) => TermColor::Rgb { r, g, b },
+        Color::Indexed(i) => TermColor::AnsiValue(i),
+    }))
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use crossterm::{
+        event::{KeyCode, KeyEvent, KeyModifiers},
+        style::Color as TermColor,
+    };
+
+    use super::{key_name, term_color};
+    use crate::{
+        evaluator::{runtime_err::ErrKind, tests::assert_err_kind, value::Value},
+        lexer::cursor::Cursor,
+    };
+
+    #[test]
+    fn set_color_args() {
+        let color = |s: &str| term_color(&Value::str(s), Cursor::new()).ok().flatten();
+        assert_eq!(color("red"), Some(TermColor::DarkRed));
+        assert_eq!(color("LightRed"), Some(TermColor::Red));
+        assert_eq!(
+            color("#0a0b0c"),
+            Some(TermColor::Rgb {
+                r: 10,
+                g: 11,
+                b: 12
+            })
+        );
+        assert_eq!(color("reset"), Some(TermColor::Reset));
+        assert!(matches!(term_color(&Value::Null, Cursor::new()), Ok(None)));
+
+        for s in ["nope", "#12", "rgb(1, 2)"] {
+            assert_err_kind(term_color(&Value::str(s), Cursor::new()), ErrKind::Value);
+        }
+        assert_err_kind(term_color(&Value::Bool(true), Cursor::new()), ErrKind::Type);
+    }
+
+    #[test]
+    fn key_names() {
+        let name = |code: KeyCode| key_name(KeyEvent::new(code, KeyModifiers::NONE));
+        assert_eq!(name(KeyCode::Up), "Up");
+        assert_eq!(name(KeyCode::Down), "Down");
+        assert_eq!(name(KeyCode::Left), "Left");
+        assert_eq!(name(KeyCode::Right), "Right");
+        assert_eq!(name(KeyCode::Char('a')), "a");
+        assert_eq!(name(KeyCode::Char('Q')), "Q");
+        assert_eq!(name(KeyCode::Char(' ')), "Space");
+        assert_eq!(name(KeyCode::Esc), "Esc");
+        assert_eq!(
+            key_name(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            "Tab"
+        );
+    }
+}
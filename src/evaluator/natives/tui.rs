@@ -1,4 +1,6 @@
 mod canvas;
+mod chart;
+mod mouse;
 mod text_input;
 
 use ordered_float::OrderedFloat;
@@ -7,9 +9,14 @@ use std::{cell::RefCell, collections::HashMap, io, rc::Rc};
 use crate::{
     evaluator::{
         Callable, ErrKind, EvalResult, Evaluator, RuntimeEvent,
-        natives::tui::{
-            canvas::{CanvasWidget, FnTuiCreateCanvas, render_canvas},
-            text_input::{FnTuiCreateTextInput, TextInputWidget, render_text_input},
+        natives::{
+            term::FnTermSize,
+            tui::{
+                canvas::{CanvasWidget, FnTuiCreateCanvas, render_canvas},
+                chart::{ChartWidget, FnTuiCreateChart, render_chart},
+                mouse::{FnTuiDisableMouse, FnTuiEnableMouse, FnTuiPollEvent},
+                text_input::{FnTuiCreateTextInput, TextInputWidget, render_text_input},
+            },
         },
         object::{Method, NativeMethod, Object},
         value::Value,
@@ -18,15 +25,21 @@ use crate::{
 };
 
 use crossterm::{
+    event::DisableMouseCapture,
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
+    },
 };
 use ratatui::{
     Frame, Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+    widgets::{
+        Block, BorderType, Borders, Gauge, List, ListItem, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Sparkline, Table, Tabs, Wrap,
+    },
 };
 
 pub fn native_tui() -> Value {
@@ -40,6 +53,10 @@ pub fn native_tui() -> Value {
         "cleanup".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiCleanup), false)),
     );
+    methods.insert(
+        "set_title".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiSetTitle), false)),
+    );
     methods.insert(
         "draw_block".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiDrawBlock), false)),
@@ -80,6 +97,26 @@ pub fn native_tui() -> Value {
         "draw_progress_rect".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiDrawProgressRect), false)),
     );
+    methods.insert(
+        "draw_tabs".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiDrawTabs), false)),
+    );
+    methods.insert(
+        "draw_tabs_rect".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiDrawTabsRect), false)),
+    );
+    methods.insert(
+        "draw_table_rect".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiDrawTableRect), false)),
+    );
+    methods.insert(
+        "draw_scrollbar_rect".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiDrawScrollbarRect), false)),
+    );
+    methods.insert(
+        "draw_sparkline_rect".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiDrawSparklineRect), false)),
+    );
     methods.insert(
         "clear".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiClear), false)),
@@ -93,10 +130,30 @@ pub fn native_tui() -> Value {
         "create_canvas".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiCreateCanvas), false)),
     );
+    methods.insert(
+        "create_chart".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiCreateChart), false)),
+    );
     methods.insert(
         "create_text_input".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiCreateTextInput), false)),
     );
+    methods.insert(
+        "enable_mouse".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiEnableMouse), false)),
+    );
+    methods.insert(
+        "disable_mouse".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiDisableMouse), false)),
+    );
+    methods.insert(
+        "poll_event".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTuiPollEvent), false)),
+    );
+    methods.insert(
+        "terminal_size".into(),
+        Method::Native(NativeMethod::new(Rc::new(FnTermSize), false)),
+    );
     methods.insert(
         "split_row".into(),
         Method::Native(NativeMethod::new(Rc::new(FnTuiSplitRow), false)),
@@ -131,11 +188,15 @@ enum Widget {
         width: u16,
         height: u16,
         text: String,
+        alignment: Alignment,
+        wrap: bool,
         style: TuiStyle,
     },
     TextRect {
         rect_id: usize,
         text: String,
+        alignment: Alignment,
+        wrap: bool,
         style: TuiStyle,
     },
     Checkbox {
@@ -165,6 +226,7 @@ enum Widget {
         rect_id: usize,
         items: Vec<String>,
         selected: usize,
+        offset: usize,
         style: TuiStyle,
         title: String,
     },
@@ -182,7 +244,42 @@ enum Widget {
         label: String,
         style: TuiStyle,
     },
+    Tabs {
+        x: u16,
+        y: u16,
+        width: u16,
+        titles: Vec<String>,
+        selected: usize,
+        style: TuiStyle,
+    },
+    TabsRect {
+        rect_id: usize,
+        titles: Vec<String>,
+        selected: usize,
+        style: TuiStyle,
+    },
+    TableRect {
+        rect_id: usize,
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+        selected: usize,
+        style: TuiStyle,
+    },
+    ScrollbarRect {
+        rect_id: usize,
+        position: usize,
+        total: usize,
+        style: TuiStyle,
+    },
+    SparklineRect {
+        rect_id: usize,
+        data: Vec<u64>,
+        // None scales the bars to the largest value in data
+        max: Option<u64>,
+        style: TuiStyle,
+    },
     Canvas(CanvasWidget),
+    Chart(ChartWidget),
     TextInput(TextInputWidget),
 }
 
@@ -198,11 +295,7 @@ impl Widget {
                 style,
             } => {
                 let area = Rect::new(*x, *y, *width, *height);
-                let block = Block::default()
-                    .title(title.clone())
-                    .borders(Borders::ALL)
-                    .style(style.text_style())
-                    .border_style(Style::default().fg(style.accent));
+                let block = style.block().title(title.clone()).style(style.text_style());
                 frame.render_widget(block, area);
             }
             Widget::BlockRect {
@@ -211,11 +304,7 @@ impl Widget {
                 style,
             } => {
                 if let Some(area) = rect_from_id(*rect_id, frame) {
-                    let block = Block::default()
-                        .title(title.clone())
-                        .borders(Borders::ALL)
-                        .style(style.text_style())
-                        .border_style(Style::default().fg(style.accent));
+                    let block = style.block().title(title.clone()).style(style.text_style());
                     frame.render_widget(block, area);
                 }
             }
@@ -225,24 +314,22 @@ impl Widget {
                 width,
                 height,
                 text,
+                alignment,
+                wrap,
                 style,
             } => {
                 let area = Rect::new(*x, *y, *width, *height);
-                let paragraph = Paragraph::new(text.clone())
-                    .style(style.text_style())
-                    .wrap(Wrap { trim: false });
-                frame.render_widget(paragraph, area);
+                frame.render_widget(paragraph(text, *alignment, *wrap, style), area);
             }
             Widget::TextRect {
                 rect_id,
                 text,
+                alignment,
+                wrap,
                 style,
             } => {
                 if let Some(area) = rect_from_id(*rect_id, frame) {
-                    let paragraph = Paragraph::new(text.clone())
-                        .style(style.text_style())
-                        .wrap(Wrap { trim: false });
-                    frame.render_widget(paragraph, area);
+                    frame.render_widget(paragraph(text, *alignment, *wrap, style), area);
                 }
             }
             Widget::Checkbox {
@@ -299,63 +386,20 @@ impl Widget {
                 title,
             } => {
                 let area = Rect::new(*x, *y, *width, *height);
-                let normal = style.text_style();
-                let highlight = Style::default()
-                    .fg(style.accent)
-                    .bg(style.bg)
-                    .add_modifier(Modifier::BOLD);
-
-                let list_items: Vec<ListItem> = items
-                    .iter()
-                    .enumerate()
-                    .map(|(i, item)| {
-                        let prefix = if i == *selected { "> " } else { "  " };
-                        let item_style = if i == *selected { highlight } else { normal };
-                        ListItem::new(format!("{}{}", prefix, item)).style(item_style)
-                    })
-                    .collect();
-
-                let list = List::new(list_items).block(
-                    Block::default()
-                        .title(title.clone())
-                        .borders(Borders::ALL)
-                        .border_style(Style::default().fg(style.accent)),
-                );
-
-                frame.render_widget(list, area);
+                let offset = list_offset(*selected, items.len(), list_height(area), 0);
+                frame.render_widget(list(items, *selected, offset, style, title), area);
             }
             Widget::ListRect {
                 rect_id,
                 items,
                 selected,
+                offset,
                 style,
                 title,
             } => {
                 if let Some(area) = rect_from_id(*rect_id, frame) {
-                    let normal = style.text_style();
-                    let highlight = Style::default()
-                        .fg(style.accent)
-                        .bg(style.bg)
-                        .add_modifier(Modifier::BOLD);
-
-                    let list_items: Vec<ListItem> = items
-                        .iter()
-                        .enumerate()
-                        .map(|(i, item)| {
-                            let prefix = if i == *selected { "> " } else { "  " };
-                            let item_style = if i == *selected { highlight } else { normal };
-                            ListItem::new(format!("{}{}", prefix, item)).style(item_style)
-                        })
-                        .collect();
-
-                    let list = List::new(list_items).block(
-                        Block::default()
-                            .title(title.clone())
-                            .borders(Borders::ALL)
-                            .border_style(Style::default().fg(style.accent)),
-                    );
-
-                    frame.render_widget(list, area);
+                    let offset = list_offset(*selected, items.len(), list_height(area), *offset);
+                    frame.render_widget(list(items, *selected, offset, style, title), area);
                 }
             }
             Widget::Progress {
@@ -397,11 +441,126 @@ impl Widget {
                     frame.render_widget(gauge, area);
                 }
             }
-            Widget::Canvas(widget) => render_canvas(
-                frame,
-                widget,
-                widget_rect(frame, widget.x, widget.y, widget.width, widget.height),
-            ),
+            Widget::Tabs {
+                x,
+                y,
+                width,
+                titles,
+                selected,
+                style,
+            } => {
+                let area = Rect::new(*x, *y, *width, 3);
+                frame.render_widget(tabs(titles, *selected, style), area);
+            }
+            Widget::TabsRect {
+                rect_id,
+                titles,
+                selected,
+                style,
+            } => {
+                if let Some(area) = rect_from_id(*rect_id, frame) {
+                    frame.render_widget(tabs(titles, *selected, style), area);
+                }
+            }
+            Widget::TableRect {
+                rect_id,
+                headers,
+                rows,
+                selected,
+                style,
+            } => {
+                if let Some(area) = rect_from_id(*rect_id, frame) {
+                    let highlight = Style::default()
+                        .fg(style.accent)
+                        .bg(style.bg)
+                        .add_modifier(Modifier::BOLD);
+
+                    let table_rows: Vec<Row> = rows
+                        .iter()
+                        .enumerate()
+                        .map(|(i, cells)| {
+                            let row_style = if i == *selected {
+                                highlight
+                            } else {
+                                style.text_style()
+                            };
+                            Row::new(cells.iter().map(String::as_str)).style(row_style)
+                        })
+                        .collect();
+
+                    let columns = headers.len().max(1) as u32;
+                    let widths = vec![Constraint::Ratio(1, columns); columns as usize];
+                    let table = Table::new(table_rows, widths)
+                        .header(
+                            Row::new(headers.iter().map(String::as_str))
+                                .style(style.accent_style().add_modifier(Modifier::BOLD)),
+                        )
+                        .block(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .border_style(Style::default().fg(style.accent)),
+                        );
+                    frame.render_widget(table, area);
+                }
+            }
+            Widget::ScrollbarRect {
+                rect_id,
+                position,
+                total,
+                style,
+            } => {
+                if let Some(area) = rect_from_id(*rect_id, frame) {
+                    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                        .style(style.text_style())
+                        .thumb_style(style.accent_style());
+                    let mut state = ScrollbarState::new(*total).position(*position);
+                    frame.render_stateful_widget(scrollbar, area, &mut state);
+                }
+            }
+            Widget::SparklineRect {
+                rect_id,
+                data,
+                max,
+                style,
+            } => {
+                if let Some(area) = rect_from_id(*rect_id, frame) {
+                    let mut sparkline = Sparkline::default().data(data).style(style.accent_style());
+                    if let Some(max) = max {
+                        sparkline = sparkline.max(*max);
+                    }
+                    frame.render_widget(sparkline, area);
+                }
+            }
+            Widget::Canvas(widget) => {
+                let area = match widget.rect_id {
+                    Some(id) => rect_from_id(id, frame),
+                    None => Some(widget_rect(
+                        frame,
+                        widget.x,
+                        widget.y,
+                        widget.width,
+                        widget.height,
+                    )),
+                };
+                if let Some(area) = area {
+                    render_canvas(frame, widget, area);
+                }
+            }
+            Widget::Chart(widget) => {
+                let area = match widget.rect_id {
+                    Some(id) => rect_from_id(id, frame),
+                    None => Some(widget_rect(
+                        frame,
+                        widget.x,
+                        widget.y,
+                        widget.width,
+                        widget.height,
+                    )),
+                };
+                if let Some(area) = area {
+                    render_chart(frame, widget, area);
+                }
+            }
             Widget::TextInput(widget) => render_text_input(
                 frame,
                 widget,
@@ -411,6 +570,93 @@ impl Widget {
     }
 }
 
+fn paragraph<'a>(
+    text: &'a str,
+    alignment: Alignment,
+    wrap: bool,
+    style: &TuiStyle,
+) -> Paragraph<'a> {
+    let paragraph = Paragraph::new(text)
+        .style(style.text_style())
+        .alignment(alignment);
+    if wrap {
+        paragraph.wrap(Wrap { trim: false })
+    } else {
+        paragraph
+    }
+}
+
+fn list<'a>(
+    items: &'a [String],
+    selected: usize,
+    offset: usize,
+    style: &TuiStyle,
+    title: &'a str,
+) -> List<'a> {
+    let normal = style.text_style();
+    let highlight = Style::default()
+        .fg(style.accent)
+        .bg(style.bg)
+        .add_modifier(Modifier::BOLD);
+
+    let list_items: Vec<ListItem> = items
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .map(|(i, item)| {
+            let prefix = if i == selected { "> " } else { "  " };
+            let item_style = if i == selected { highlight } else { normal };
+            ListItem::new(format!("{}{}", prefix, item)).style(item_style)
+        })
+        .collect();
+
+    List::new(list_items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(style.accent)),
+    )
+}
+
+// Number of list items that fit inside the borders of `area`
+fn list_height(area: Rect) -> usize {
+    area.height.saturating_sub(2) as usize
+}
+
+// Index of the first visible item, starts from the requested offset and scrolls just enough to
+// keep the selected item in view
+fn list_offset(selected: usize, len: usize, height: usize, offset: usize) -> usize {
+    if height == 0 || len <= height {
+        return 0;
+    }
+
+    let selected = selected.min(len - 1);
+    let offset = if selected < offset {
+        selected
+    } else if selected >= offset + height {
+        selected + 1 - height
+    } else {
+        offset
+    };
+    offset.min(len - height)
+}
+
+fn tabs<'a>(titles: &'a [String], selected: usize, style: &TuiStyle) -> Tabs<'a> {
+    Tabs::new(titles.iter().map(String::as_str))
+        .select(selected)
+        .style(style.text_style())
+        .highlight_style(
+            Style::default()
+                .fg(style.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(style.accent)),
+        )
+}
+
 pub(super) fn widget_rect(frame: &Frame<'_>, x: u16, y: u16, width: u16, height: u16) -> Rect {
     let parent = frame.area();
     let y = y.min(parent.height);
@@ -476,6 +722,8 @@ pub struct TuiStyle {
     pub fg: Color,
     pub bg: Color,
     pub accent: Color,
+    // None draws no border at all
+    pub border: Option<BorderType>,
 }
 
 impl Default for TuiStyle {
@@ -484,6 +732,7 @@ impl Default for TuiStyle {
             fg: Color::White,
             bg: Color::Reset,
             accent: Color::Cyan,
+            border: Some(BorderType::Plain),
         }
     }
 }
@@ -512,6 +761,26 @@ impl TuiStyle {
         self
     }
 
+    fn with_border(mut self, border: Option<BorderType>) -> Self {
+        self.border = border;
+        self
+    }
+
+    // "none", "plain", "rounded", "double" or "thick", anything else falls back to plain
+    fn border_from_value(val: Option<&Value>) -> Option<BorderType> {
+        let kind = match val {
+            Some(Value::Str(s)) => s.borrow().trim().to_lowercase(),
+            _ => return Some(BorderType::Plain),
+        };
+        match kind.as_str() {
+            "none" => None,
+            "rounded" => Some(BorderType::Rounded),
+            "double" => Some(BorderType::Double),
+            "thick" => Some(BorderType::Thick),
+            _ => Some(BorderType::Plain),
+        }
+    }
+
     fn from_args(
         fg_arg: Option<&Value>,
         bg_arg: Option<&Value>,
@@ -527,6 +796,16 @@ impl TuiStyle {
         Style::default().fg(self.fg).bg(self.bg)
     }
 
+    fn block(&self) -> Block<'static> {
+        match self.border {
+            Some(border) => Block::default()
+                .borders(Borders::ALL)
+                .border_type(border)
+                .border_style(self.accent_style()),
+            None => Block::default(),
+        }
+    }
+
     fn accent_style(&self) -> Style {
         Style::default().fg(self.accent)
     }
@@ -548,6 +827,8 @@ thread_local! {
     static LAYOUT_CMDS: RefCell<Vec<LayoutCmd>> = RefCell::new(Vec::new());
     static NEXT_RECT_ID: RefCell<usize> = RefCell::new(1); // 0 is root
     static RECTS: RefCell<Vec<Rect>> = RefCell::new(Vec::new());
+    // Whether Tui.set_title changed the window title, so cleanup knows to clear it
+    static TITLE_SET: RefCell<bool> = const { RefCell::new(false) };
 }
 
 #[derive(Clone)]
@@ -581,18 +862,56 @@ native_fn!(
     "tui_cleanup",
     0,
     |_evaluator, _args, _cursor| {
-        TERMINAL.with(|t| {
-            if let Some(mut terminal) = t.borrow_mut().take() {
-                let _ = disable_raw_mode();
-                let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
-                let _ = terminal.show_cursor();
-            }
-        });
+        restore_terminal();
+        Ok(Value::Null)
+    }
+);
 
+// Tui.set_title(title): sets the terminal window title, Tui.cleanup() clears it again
+native_fn!(
+    FnTuiSetTitle,
+    "tui_set_title",
+    1,
+    |_evaluator, args, cursor| {
+        let title = args[0].check_str(cursor, Some("title".into()))?;
+        execute!(io::stdout(), SetTitle(title.borrow().as_str()))?;
+        TITLE_SET.with(|t| *t.borrow_mut() = true);
         Ok(Value::Null)
     }
 );
 
+// The command that undoes Tui.set_title, None if the title was never changed. Terminals
+// don't report their current title so it's cleared instead of restored
+fn take_title_reset() -> Option<SetTitle<&'static str>> {
+    TITLE_SET.with(|t| t.replace(false)).then_some(SetTitle(""))
+}
+
+// Restore the terminal if the TUI is active, also used by Sys.exit()
+pub fn restore_terminal() {
+    if let Some(reset) = take_title_reset() {
+        let _ = execute!(io::stdout(), reset);
+    }
+    TERMINAL.with(|t| {
+        if let Some(mut terminal) = t.borrow_mut().take() {
+            let _ = disable_raw_mode();
+            let _ = execute!(
+                terminal.backend_mut(),
+                LeaveAlternateScreen,
+                DisableMouseCapture
+            );
+            let _ = terminal.show_cursor();
+        }
+    });
+}
+
+// Restore the terminal and drop all widgets and layout rects, used before --watch reruns a
+// program
+pub fn reset() {
+    restore_terminal();
+    WIDGETS.with(|w| w.borrow_mut().clear());
+    reset_layout_state();
+}
+
 // Tui.clear(): clears the widget buffer (call this at the start of each frame)
 native_fn!(FnTuiClear, "tui_clear", 0, |_evaluator, _args, _cursor| {
     WIDGETS.with(|w| {
@@ -628,11 +947,12 @@ native_fn!(
     }
 );
 
-// Tui.draw_block(x, y, width, height, title, border_color)
+// Tui.draw_block(x, y, width, height, title, border_color, border_kind)
+// border_kind: "none", "plain", "rounded", "double" or "thick", defaults to "plain"
 native_fn!(
     FnTuiDrawBlock,
     "tui_draw_block",
-    6,
+    6..=7,
     |_evaluator, args, cursor| {
         let x = args[0].check_num(cursor, Some("x position".into()))? as u16;
         let y = args[1].check_num(cursor, Some("y position".into()))? as u16;
@@ -640,7 +960,8 @@ native_fn!(
         let height = args[3].check_num(cursor, Some("height".into()))? as u16;
 
         let title = string_from_value(&args[4]);
-        let style = TuiStyle::from_args(None, None, args.get(5));
+        let style = TuiStyle::from_args(None, None, args.get(5))
+            .with_border(TuiStyle::border_from_value(args.get(6)));
 
         WIDGETS.with(|w| {
             w.borrow_mut().push(Widget::Block {
@@ -657,15 +978,16 @@ native_fn!(
     }
 );
 
-// Tui.draw_block_rect(rect_id, title, border_color)
+// Tui.draw_block_rect(rect_id, title, border_color, border_kind)
 native_fn!(
     FnTuiDrawBlockRect,
     "tui_draw_block_rect",
-    3,
+    3..=4,
     |_evaluator, args, cursor| {
         let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
         let title = string_from_value(&args[1]);
-        let style = TuiStyle::from_args(None, None, args.get(2));
+        let style = TuiStyle::from_args(None, None, args.get(2))
+            .with_border(TuiStyle::border_from_value(args.get(3)));
 
         WIDGETS.with(|w| {
             w.borrow_mut().push(Widget::BlockRect {
@@ -679,11 +1001,12 @@ native_fn!(
     }
 );
 
-// Tui.draw_text(x, y, width, height, text, fg_color, bg_color)
+// Tui.draw_text(x, y, width, height, text, fg_color, bg_color, align?, wrap?)
+// align: "left", "center" or "right", wrap: wrap long lines, true by default
 native_fn!(
     FnTuiDrawText,
     "tui_draw_text",
-    7,
+    7..=9,
     |_evaluator, args, cursor| {
         let x = args[0].check_num(cursor, Some("x position".into()))? as u16;
         let y = args[1].check_num(cursor, Some("y position".into()))? as u16;
@@ -692,6 +1015,8 @@ native_fn!(
 
         let text = string_from_value(&args[4]);
         let style = TuiStyle::from_args(args.get(5), args.get(6), None);
+        let alignment = alignment_from_value(args.get(7));
+        let wrap = wrap_from_value(args.get(8), cursor)?;
 
         WIDGETS.with(|w| {
             w.borrow_mut().push(Widget::Text {
@@ -700,6 +1025,8 @@ native_fn!(
                 width,
                 height,
                 text,
+                alignment,
+                wrap,
                 style,
             });
         });
@@ -708,20 +1035,24 @@ native_fn!(
     }
 );
 
-// Tui.draw_text_rect(rect_id, text, fg_color, bg_color)
+// Tui.draw_text_rect(rect_id, text, fg_color, bg_color, align?, wrap?)
 native_fn!(
     FnTuiDrawTextRect,
     "tui_draw_text_rect",
-    4,
+    4..=6,
     |_evaluator, args, cursor| {
         let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
         let text = string_from_value(&args[1]);
         let style = TuiStyle::from_args(args.get(2), args.get(3), None);
+        let alignment = alignment_from_value(args.get(4));
+        let wrap = wrap_from_value(args.get(5), cursor)?;
 
         WIDGETS.with(|w| {
             w.borrow_mut().push(Widget::TextRect {
                 rect_id,
                 text,
+                alignment,
+                wrap,
                 style,
             });
         });
@@ -837,11 +1168,12 @@ native_fn!(
     }
 );
 
-// Tui.draw_list_rect(rect_id, items, selected, color, title)
+// Tui.draw_list_rect(rect_id, items, selected, color, title, offset?)
+// offset: index of the first visible item, the list still scrolls to keep the selection visible
 native_fn!(
     FnTuiDrawListRect,
     "tui_draw_list_rect",
-    5,
+    5..=6,
     |_evaluator, args, cursor| {
         let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
 
@@ -862,12 +1194,19 @@ native_fn!(
 
         let style = TuiStyle::from_args(None, None, args.get(3));
         let title = string_from_value(&args[4]);
+        let offset = match args.get(5) {
+            Some(val) => val
+                .check_num(cursor, Some("scroll offset".into()))?
+                .max(0.0) as usize,
+            None => 0,
+        };
 
         WIDGETS.with(|w| {
             w.borrow_mut().push(Widget::ListRect {
                 rect_id,
                 items,
                 selected,
+                offset,
                 style,
                 title,
             });
@@ -877,6 +1216,72 @@ native_fn!(
     }
 );
 
+// Tui.draw_scrollbar_rect(rect_id, position, total, color)
+// position: index of the current item out of `total` items
+native_fn!(
+    FnTuiDrawScrollbarRect,
+    "tui_draw_scrollbar_rect",
+    3..=4,
+    |_evaluator, args, cursor| {
+        let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
+        let position = args[1].check_num(cursor, Some("position".into()))?.max(0.0) as usize;
+        let total = args[2].check_num(cursor, Some("total".into()))?.max(0.0) as usize;
+        let style = TuiStyle::from_args(None, None, args.get(3));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::ScrollbarRect {
+                rect_id,
+                position,
+                total,
+                style,
+            });
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Tui.draw_sparkline_rect(rect_id, data, max, color)
+// max: value of a full height bar, 0 or Null scales the bars to the largest value in data
+native_fn!(
+    FnTuiDrawSparklineRect,
+    "tui_draw_sparkline_rect",
+    3..=4,
+    |_evaluator, args, cursor| {
+        let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
+        let data = sparkline_data(&args[1], cursor)?;
+        let max = match &args[2] {
+            Value::Null => None,
+            val => Some(val.check_num(cursor, Some("max".into()))?.max(0.0).round() as u64)
+                .filter(|max| *max > 0),
+        };
+        let style = TuiStyle::from_args(None, None, args.get(3));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::SparklineRect {
+                rect_id,
+                data,
+                max,
+                style,
+            });
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Convert a List of Nums into sparkline bar heights, values are rounded and negative ones
+// become 0
+fn sparkline_data(val: &Value, cursor: crate::lexer::cursor::Cursor) -> EvalResult<Vec<u64>> {
+    let list = val.check_list(cursor, Some("sparkline data".into()))?;
+    let mut out = Vec::new();
+    for item in list.borrow().iter() {
+        let n = item.check_num(cursor, Some("sparkline value".into()))?;
+        out.push(n.max(0.0).round() as u64);
+    }
+    Ok(out)
+}
+
 // Tui.draw_progress_rect(rect_id, percent, label, color)
 native_fn!(
     FnTuiDrawProgressRect,
@@ -927,7 +1332,120 @@ native_fn!(
     }
 );
 
-// Split utilities: percent-only constraints for simplicity
+// Tui.draw_tabs(x, y, width, titles, selected, color)
+// titles: List of strings, selected: index of the highlighted tab
+native_fn!(
+    FnTuiDrawTabs,
+    "tui_draw_tabs",
+    6,
+    |_evaluator, args, cursor| {
+        let x = args[0].check_num(cursor, Some("x".into()))? as u16;
+        let y = args[1].check_num(cursor, Some("y".into()))? as u16;
+        let width = args[2].check_num(cursor, Some("width".into()))? as u16;
+        let titles = strings_from_value(&args[3]);
+        let selected = clamp_selected(
+            args[4].check_num(cursor, Some("selected index".into()))?,
+            titles.len(),
+        );
+        let style = TuiStyle::from_args(None, None, args.get(5));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::Tabs {
+                x,
+                y,
+                width,
+                titles,
+                selected,
+                style,
+            });
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Tui.draw_tabs_rect(rect_id, titles, selected, color)
+native_fn!(
+    FnTuiDrawTabsRect,
+    "tui_draw_tabs_rect",
+    4,
+    |_evaluator, args, cursor| {
+        let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
+        let titles = strings_from_value(&args[1]);
+        let selected = clamp_selected(
+            args[2].check_num(cursor, Some("selected index".into()))?,
+            titles.len(),
+        );
+        let style = TuiStyle::from_args(None, None, args.get(3));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::TabsRect {
+                rect_id,
+                titles,
+                selected,
+                style,
+            });
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Tui.draw_table_rect(rect_id, headers, rows, selected, color)
+// headers: List of strings, rows: List of Lists of strings, selected: index of selected row
+native_fn!(
+    FnTuiDrawTableRect,
+    "tui_draw_table_rect",
+    5,
+    |_evaluator, args, cursor| {
+        let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
+        let headers = strings_from_value(&args[1]);
+        let rows = table_rows(&args[2], headers.len(), cursor)?;
+        let selected = clamp_selected(
+            args[3].check_num(cursor, Some("selected index".into()))?,
+            rows.len(),
+        );
+        let style = TuiStyle::from_args(None, None, args.get(4));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::TableRect {
+                rect_id,
+                headers,
+                rows,
+                selected,
+                style,
+            });
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Convert a List of Lists into table cells, every row is padded or cut to `columns` cells
+fn table_rows(
+    val: &Value,
+    columns: usize,
+    cursor: crate::lexer::cursor::Cursor,
+) -> EvalResult<Vec<Vec<String>>> {
+    let rows = val.check_list(cursor, Some("table rows".into()))?;
+    let mut out = Vec::new();
+    for row in rows.borrow().iter() {
+        let mut cells = strings_from_value(&Value::List(
+            row.check_list(cursor, Some("table row".into()))?,
+        ));
+        cells.resize(columns, String::new());
+        out.push(cells);
+    }
+    Ok(out)
+}
+
+// Clamp a selected index into 0..len so out of range selections highlight the nearest item
+fn clamp_selected(selected: f64, len: usize) -> usize {
+    (selected.max(0.0) as usize).min(len.saturating_sub(1))
+}
+
+// Split utilities: numbers are percentages, "len:N", "min:N" and "max:N" strings map to
+// fixed length, minimum and maximum constraints in cells
 fn constraints_from_value(
     val: &Value,
     cursor: crate::lexer::cursor::Cursor,
@@ -935,22 +1453,47 @@ fn constraints_from_value(
     if let Value::List(list) = val {
         let mut out = Vec::new();
         for v in list.borrow().iter() {
-            let p = v
-                .check_num(cursor, Some("constraint".into()))?
-                .clamp(0.0, 100.0);
-            out.push(Constraint::Percentage(p as u16));
+            let constraint = match v {
+                Value::Str(tag) => constraint_from_tag(&tag.borrow(), cursor)?,
+                _ => {
+                    let p = v
+                        .check_num(cursor, Some("constraint".into()))?
+                        .clamp(0.0, 100.0);
+                    Constraint::Percentage(p as u16)
+                }
+            };
+            out.push(constraint);
         }
         Ok(out)
     } else {
         Err(RuntimeEvent::error(
             ErrKind::Type,
-            "constraints must be a List of numbers (percentages)".into(),
+            "constraints must be a List of numbers (percentages) or Strs (eg. \"len:3\")".into(),
             cursor,
         ))
     }
 }
 
-// Tui.split_row(parent_rect_id, constraints:list<num>) -> list<num rect_ids>
+fn constraint_from_tag(tag: &str, cursor: crate::lexer::cursor::Cursor) -> EvalResult<Constraint> {
+    let parsed = tag
+        .split_once(':')
+        .and_then(|(kind, n)| Some((kind.trim(), n.trim().parse::<u16>().ok()?)));
+    match parsed {
+        Some(("len", n)) => Ok(Constraint::Length(n)),
+        Some(("min", n)) => Ok(Constraint::Min(n)),
+        Some(("max", n)) => Ok(Constraint::Max(n)),
+        _ => Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!(
+                "invalid constraint '{}', expected \"len:N\", \"min:N\" or \"max:N\"",
+                tag
+            ),
+            cursor,
+        )),
+    }
+}
+
+// Tui.split_row(parent_rect_id, constraints:list<num or str>) -> list<num rect_ids>
 native_fn!(
     FnTuiSplitRow,
     "tui_split_row",
@@ -981,7 +1524,7 @@ native_fn!(
     }
 );
 
-// Tui.split_col(parent_rect_id, constraints:list<num>) -> list<num rect_ids>
+// Tui.split_col(parent_rect_id, constraints:list<num or str>) -> list<num rect_ids>
 native_fn!(
     FnTuiSplitCol,
     "tui_split_col",
@@ -1012,9 +1555,26 @@ native_fn!(
     }
 );
 
-// Helper function to parse color strings
+/// Parse a color name, a "#rrggbb" hex string or an "rgb(r, g, b)" string, anything else
+/// falls back to white
 pub fn parse_color(s: &str) -> Color {
-    match s.to_lowercase().as_str() {
+    try_parse_color(s).unwrap_or(Color::White)
+}
+
+/// Same as `parse_color` but returns None for unknown or malformed colors
+pub fn try_parse_color(s: &str) -> Option<Color> {
+    let lower = s.trim().to_lowercase();
+    if let Some(hex) = lower.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+    if let Some(rgb) = lower
+        .strip_prefix("rgb(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return parse_rgb_color(rgb);
+    }
+
+    let color = match lower.as_str() {
         "black" => Color::Black,
         "red" => Color::Red,
         "green" => Color::Green,
@@ -1031,7 +1591,47 @@ pub fn parse_color(s: &str) -> Color {
         "lightblue" => Color::LightBlue,
         "lightmagenta" => Color::LightMagenta,
         "lightcyan" => Color::LightCyan,
-        _ => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn parse_rgb_color(rgb: &str) -> Option<Color> {
+    let channels = rgb
+        .split(',')
+        .map(|c| c.trim().parse::<u8>().ok())
+        .collect::<Option<Vec<u8>>>()?;
+    match channels.as_slice() {
+        [r, g, b] => Some(Color::Rgb(*r, *g, *b)),
+        _ => None,
+    }
+}
+
+// Text alignment from "left", "center" or "right", anything else is left aligned
+fn alignment_from_value(val: Option<&Value>) -> Alignment {
+    let Some(Value::Str(s)) = val else {
+        return Alignment::Left;
+    };
+    match s.borrow().trim().to_lowercase().as_str() {
+        "center" => Alignment::Center,
+        "right" => Alignment::Right,
+        _ => Alignment::Left,
+    }
+}
+
+// Whether text wraps, defaults to true when the argument is left out
+fn wrap_from_value(val: Option<&Value>, cursor: crate::lexer::cursor::Cursor) -> EvalResult<bool> {
+    match val {
+        Some(val) => val.check_bool(cursor, Some("wrap flag".into())),
+        None => Ok(true),
     }
 }
 
@@ -1041,3 +1641,294 @@ fn string_from_value(value: &Value) -> String {
         _ => String::new(),
     }
 }
+
+fn strings_from_value(value: &Value) -> Vec<String> {
+    match value {
+        Value::List(list) => list.borrow().iter().map(|v| v.to_string()).collect(),
+        _ => vec![],
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::Command;
+    use ratatui::{
+        layout::{Alignment, Constraint},
+        style::Color,
+        widgets::BorderType,
+    };
+
+    use super::{
+        TITLE_SET, TuiStyle, WIDGETS, Widget, alignment_from_value, constraints_from_value,
+        list_offset, parse_color, sparkline_data, table_rows, take_title_reset,
+    };
+    use crate::{
+        evaluator::{
+            runtime_err::ErrKind,
+            tests::{assert_err_kind, run},
+            value::Value,
+        },
+        lexer::cursor::Cursor,
+    };
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(parse_color("red"), Color::Red);
+        assert_eq!(parse_color("LightBlue"), Color::LightBlue);
+        assert_eq!(parse_color("nope"), Color::White);
+    }
+
+    #[test]
+    fn rgb_colors() {
+        assert_eq!(parse_color("#ff8800"), Color::Rgb(255, 136, 0));
+        assert_eq!(parse_color("#FF8800"), Color::Rgb(255, 136, 0));
+        assert_eq!(parse_color("rgb(255,136,0)"), Color::Rgb(255, 136, 0));
+        assert_eq!(parse_color("rgb( 1, 2, 3 )"), Color::Rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn malformed_colors() {
+        assert_eq!(parse_color("#zz"), Color::White);
+        assert_eq!(parse_color("#zzzzzz"), Color::White);
+        assert_eq!(parse_color("#ff880"), Color::White);
+        // multi-byte chars must not panic on slicing
+        assert_eq!(parse_color("#ééé"), Color::White);
+        assert_eq!(parse_color("rgb(256,0,0)"), Color::White);
+        assert_eq!(parse_color("rgb(1,2)"), Color::White);
+    }
+
+    #[test]
+    fn tagged_constraints() {
+        let list = |items: Vec<Value>| Value::List(Rc::new(RefCell::new(items)));
+
+        let constraints = list(vec![Value::str("len:3"), Value::Num(100.0.into())]);
+        assert_eq!(
+            constraints_from_value(&constraints, Cursor::new()).unwrap(),
+            vec![Constraint::Length(3), Constraint::Percentage(100)]
+        );
+        let constraints = list(vec![Value::str("min:5"), Value::str("max: 10")]);
+        assert_eq!(
+            constraints_from_value(&constraints, Cursor::new()).unwrap(),
+            vec![Constraint::Min(5), Constraint::Max(10)]
+        );
+
+        for tag in ["len", "len:-1", "width:3", "min:x"] {
+            assert_err_kind(
+                constraints_from_value(&list(vec![Value::str(tag)]), Cursor::new()),
+                ErrKind::Value,
+            );
+        }
+    }
+
+    #[test]
+    fn border_kinds() {
+        let kind = |s: &str| TuiStyle::border_from_value(Some(&Value::str(s)));
+        assert_eq!(kind("none"), None);
+        assert_eq!(kind("Rounded"), Some(BorderType::Rounded));
+        assert_eq!(kind("double"), Some(BorderType::Double));
+        assert_eq!(kind("wavy"), Some(BorderType::Plain));
+        assert_eq!(TuiStyle::border_from_value(None), Some(BorderType::Plain));
+
+        let (result, _) = run("Tui.draw_block_rect(0, \"title\", \"red\", \"none\")");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::BlockRect { style, .. }) => assert_eq!(style.border, None),
+            _ => panic!("expected a BlockRect widget"),
+        });
+    }
+
+    #[test]
+    fn terminal_size() {
+        let (result, globals) = run("var size = Tui.terminal_size()");
+        match result {
+            Ok(()) => {
+                let size = globals.borrow().get("size", Cursor::new()).unwrap();
+                let Value::List(size) = size else {
+                    panic!("expected a List");
+                };
+                assert_eq!(size.borrow().len(), 2);
+                assert!(size.borrow().iter().all(|v| matches!(v, Value::Num(_))));
+            }
+            // no terminal attached, eg. when tests run with captured output
+            Err(_) => assert_err_kind(result, ErrKind::IO),
+        }
+    }
+
+    #[test]
+    fn draw_tabs() {
+        let (result, _) = run("Tui.draw_tabs_rect(0, [\"One\", \"Two\"], 5, \"red\")");
+        assert!(result.is_ok());
+
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::TabsRect {
+                rect_id,
+                titles,
+                selected,
+                style,
+            }) => {
+                assert_eq!(*rect_id, 0);
+                assert_eq!(titles, &["One", "Two"]);
+                assert_eq!(*selected, 1);
+                assert_eq!(style.accent, Color::Red);
+            }
+            _ => panic!("expected a TabsRect widget"),
+        });
+    }
+
+    #[test]
+    fn set_title() {
+        let (result, _) = run("Tui.set_title(42)");
+        assert_err_kind(result, ErrKind::Type);
+        assert!(take_title_reset().is_none());
+
+        TITLE_SET.with(|t| *t.borrow_mut() = true);
+        let (result, _) = run("Tui.cleanup()");
+        assert!(result.is_ok());
+        // cleanup consumed the pending reset
+        assert!(take_title_reset().is_none());
+
+        TITLE_SET.with(|t| *t.borrow_mut() = true);
+        let mut ansi = String::new();
+        take_title_reset()
+            .expect("a title reset after set_title")
+            .write_ansi(&mut ansi)
+            .unwrap();
+        assert_eq!(ansi, "\x1B]0;\x07");
+    }
+
+    #[test]
+    fn sparkline_from_nums() {
+        let data = Value::List(Rc::new(RefCell::new(vec![
+            Value::Num(3.0.into()),
+            Value::Num(1.6.into()),
+            Value::Num((-2.0).into()),
+        ])));
+        assert_eq!(sparkline_data(&data, Cursor::new()).unwrap(), vec![3, 2, 0]);
+
+        let (result, _) = run("Tui.draw_sparkline_rect(0, [1, 5, 2], 0, \"green\")");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::SparklineRect {
+                data, max, style, ..
+            }) => {
+                assert_eq!(data, &[1, 5, 2]);
+                assert_eq!(*max, None);
+                assert_eq!(style.accent, Color::Green);
+            }
+            _ => panic!("expected a SparklineRect widget"),
+        });
+
+        let (result, _) = run("Tui.draw_sparkline_rect(0, [1, 5], 10)");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::SparklineRect { max, .. }) => assert_eq!(*max, Some(10)),
+            _ => panic!("expected a SparklineRect widget"),
+        });
+
+        let (result, _) = run("Tui.draw_sparkline_rect(0, [1, \"a\"], Null)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn table_rows_from_lists() {
+        let (result, _) =
+            run("Tui.draw_table_rect(0, [\"a\", \"b\"], [[1, 2, 3], [\"x\"]], 0, \"red\")");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::TableRect { headers, rows, .. }) => {
+                assert_eq!(headers, &["a", "b"]);
+                assert_eq!(rows, &[vec!["1", "2"], vec!["x", ""]]);
+            }
+            _ => panic!("expected a TableRect widget"),
+        });
+
+        let rows = Value::List(Rc::new(RefCell::new(vec![Value::Num(1.0.into())])));
+        assert_err_kind(table_rows(&rows, 2, Cursor::new()), ErrKind::Type);
+    }
+
+    #[test]
+    fn list_scroll_offset() {
+        // 10 items in a list with room for 4 of them
+        assert_eq!(list_offset(2, 10, 4, 0), 0);
+        assert_eq!(list_offset(4, 10, 4, 0), 1);
+        assert_eq!(list_offset(9, 10, 4, 0), 6);
+        assert_eq!(list_offset(20, 10, 4, 0), 6);
+        // a requested offset is kept while the selection is visible
+        assert_eq!(list_offset(5, 10, 4, 3), 3);
+        assert_eq!(list_offset(1, 10, 4, 3), 1);
+        assert_eq!(list_offset(5, 10, 4, 9), 5);
+        // short lists never scroll
+        assert_eq!(list_offset(2, 3, 4, 2), 0);
+        assert_eq!(list_offset(2, 10, 0, 0), 0);
+
+        let (result, _) = run("Tui.draw_list_rect(0, [1, 2, 3], 2, \"blue\", \"\", 1)\n\
+             Tui.draw_scrollbar_rect(0, 2, 3)");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| {
+            let widgets = w.borrow();
+            assert!(matches!(
+                widgets[widgets.len() - 2],
+                Widget::ListRect { offset: 1, .. }
+            ));
+            assert!(matches!(
+                widgets[widgets.len() - 1],
+                Widget::ScrollbarRect {
+                    position: 2,
+                    total: 3,
+                    ..
+                }
+            ));
+        });
+    }
+
+    #[test]
+    fn text_alignment() {
+        assert_eq!(
+            alignment_from_value(Some(&Value::str("center"))),
+            Alignment::Center
+        );
+        assert_eq!(
+            alignment_from_value(Some(&Value::str(" Right "))),
+            Alignment::Right
+        );
+        assert_eq!(
+            alignment_from_value(Some(&Value::str("left"))),
+            Alignment::Left
+        );
+        assert_eq!(
+            alignment_from_value(Some(&Value::str("middle"))),
+            Alignment::Left
+        );
+        assert_eq!(alignment_from_value(None), Alignment::Left);
+
+        let (result, _) =
+            run("Tui.draw_text_rect(0, \"hi\", \"white\", \"black\", \"center\", false)");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| {
+            assert!(matches!(
+                w.borrow().last(),
+                Some(Widget::TextRect {
+                    alignment: Alignment::Center,
+                    wrap: false,
+                    ..
+                })
+            ));
+        });
+
+        let (result, _) = run("Tui.draw_text(0, 0, 10, 1, \"hi\", \"white\", \"black\")");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| {
+            assert!(matches!(
+                w.borrow().last(),
+                Some(Widget::Text {
+                    alignment: Alignment::Left,
+                    wrap: true,
+                    ..
+                })
+            ));
+        });
+    }
+}
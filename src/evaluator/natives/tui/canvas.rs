@@ -8,13 +8,16 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use crate::evaluator::{
     Callable, EvalResult, Evaluator,
     object::{Method, NativeMethod, Object},
+    runtime_err::{ErrKind, RuntimeEvent},
     value::Value,
 };
+use crate::lexer::cursor::Cursor;
 
 use ratatui::{
     Frame,
     layout::Rect,
-    style::Color,
+    style::{Color, Style},
+    text::Span,
     widgets::canvas::{Canvas as RatatuiCanvas, Circle, Line, Points, Rectangle},
 };
 
@@ -81,6 +84,26 @@ native_fn!(
             )),
         );
 
+        methods.insert(
+            "polygon".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(CanvasPolygonMethod {
+                    data: Rc::clone(&canvas_data),
+                }),
+                false,
+            )),
+        );
+
+        methods.insert(
+            "text".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(CanvasTextMethod {
+                    data: Rc::clone(&canvas_data),
+                }),
+                false,
+            )),
+        );
+
         methods.insert(
             "set_bounds".into(),
             Method::Native(NativeMethod::new(
@@ -111,6 +134,16 @@ native_fn!(
             )),
         );
 
+        methods.insert(
+            "render_rect".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(CanvasRenderRectMethod {
+                    data: Rc::clone(&canvas_data),
+                }),
+                false,
+            )),
+        );
+
         Ok(Value::Obj(Rc::new(Object::new("Canvas".into(), methods))))
     }
 );
@@ -151,6 +184,17 @@ pub enum CanvasCommand {
         points: Vec<(f64, f64)>,
         color: Color,
     },
+    Text {
+        x: f64,
+        y: f64,
+        text: String,
+        color: Color,
+    },
+    // Outline through the points, closed back to the first one
+    Polygon {
+        points: Vec<(f64, f64)>,
+        color: Color,
+    },
 }
 
 #[derive(Clone)]
@@ -162,6 +206,23 @@ pub struct CanvasWidget {
     pub x_bounds: (f64, f64),
     pub y_bounds: (f64, f64),
     pub commands: Vec<CanvasCommand>,
+    // Layout rect to render into, overrides the absolute position when set
+    pub rect_id: Option<usize>,
+}
+
+impl CanvasData {
+    fn widget(&self, rect_id: Option<usize>) -> CanvasWidget {
+        CanvasWidget {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            x_bounds: self.x_bounds,
+            y_bounds: self.y_bounds,
+            commands: self.commands.clone(),
+            rect_id,
+        }
+    }
 }
 
 pub fn render_canvas(frame: &mut Frame<'_>, widget: &CanvasWidget, area: Rect) {
@@ -212,6 +273,23 @@ pub fn render_canvas(frame: &mut Frame<'_>, widget: &CanvasWidget, area: Rect) {
                         coords: points,
                         color: *color,
                     }),
+                    CanvasCommand::Text { x, y, text, color } => ctx.print(
+                        *x,
+                        *y,
+                        Span::styled(text.clone(), Style::default().fg(*color)),
+                    ),
+                    CanvasCommand::Polygon { points, color } => {
+                        // each point connects to the next one, the last one to the first
+                        for (start, end) in points.iter().zip(points.iter().cycle().skip(1)) {
+                            ctx.draw(&Line {
+                                x1: start.0,
+                                y1: start.1,
+                                x2: end.0,
+                                y2: end.1,
+                                color: *color,
+                            });
+                        }
+                    }
                 }
             }
         });
@@ -358,6 +436,83 @@ native_fn_with_data!(
     }
 );
 
+/// Read a List of [x, y] pairs, `what` names the points in error messages
+pub(super) fn points_from_value(
+    val: &Value,
+    what: &str,
+    cursor: Cursor,
+) -> EvalResult<Vec<(f64, f64)>> {
+    let list = val.check_list(cursor, Some(format!("{} points", what)))?;
+    let mut points = Vec::new();
+    for point in list.borrow().iter() {
+        let pair = point.check_list(cursor, Some(format!("{} point", what)))?;
+        let pair = pair.borrow();
+        if pair.len() != 2 {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!(
+                    "{} points must be [x, y] pairs, found a list of {} items",
+                    what,
+                    pair.len()
+                ),
+                cursor,
+            ));
+        }
+        let x = pair[0].check_num(cursor, Some("x".into()))?;
+        let y = pair[1].check_num(cursor, Some("y".into()))?;
+        points.push((x, y));
+    }
+    Ok(points)
+}
+
+// canvas.polygon(points, color): points is a List of [x, y] pairs, at least 2 of them
+native_fn_with_data!(
+    CanvasPolygonMethod,
+    "polygon",
+    2,
+    CanvasData,
+    |_evaluator, args, cursor, data| {
+        let points = points_from_value(&args[0], "polygon", cursor)?;
+        if points.len() < 2 {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!("polygon needs at least 2 points, found {}", points.len()),
+                cursor,
+            ));
+        }
+
+        let color = parse_color(&args[1].check_str(cursor, Some("color".into()))?.borrow());
+
+        data.borrow_mut()
+            .commands
+            .push(CanvasCommand::Polygon { points, color });
+
+        Ok(Value::Null)
+    }
+);
+
+native_fn_with_data!(
+    CanvasTextMethod,
+    "text",
+    4,
+    CanvasData,
+    |_evaluator, args, cursor, data| {
+        let x = args[0].check_num(cursor, Some("x".into()))?;
+        let y = args[1].check_num(cursor, Some("y".into()))?;
+        let text = args[2].check_str(cursor, Some("text".into()))?;
+        let color = parse_color(&args[3].check_str(cursor, Some("color".into()))?.borrow());
+
+        data.borrow_mut().commands.push(CanvasCommand::Text {
+            x,
+            y,
+            text: text.borrow().clone(),
+            color,
+        });
+
+        Ok(Value::Null)
+    }
+);
+
 native_fn_with_data!(
     CanvasSetBoundsMethod,
     "set_bounds",
@@ -369,6 +524,19 @@ native_fn_with_data!(
         let y_min = args[2].check_num(cursor, Some("min y".into()))?;
         let y_max = args[3].check_num(cursor, Some("max y".into()))?;
 
+        // equal bounds would make ratatui divide by a zero width range
+        let valid = x_max > x_min && y_max > y_min;
+        if !valid {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!(
+                    "invalid canvas bounds x: [{}, {}], y: [{}, {}], max must be greater than min",
+                    x_min, x_max, y_min, y_max
+                ),
+                cursor,
+            ));
+        }
+
         let mut d = data.borrow_mut();
         d.x_bounds = (x_min, x_max);
         d.y_bounds = (y_min, y_max);
@@ -394,20 +562,160 @@ native_fn_with_data!(
     0,
     CanvasData,
     |_evaluator, _args, _cursor, data| {
-        let d = data.borrow();
+        let widget = data.borrow().widget(None);
 
         WIDGETS.with(|w| {
-            w.borrow_mut().push(Widget::Canvas(CanvasWidget {
-                x: d.x,
-                y: d.y,
-                width: d.width,
-                height: d.height,
-                x_bounds: d.x_bounds,
-                y_bounds: d.y_bounds,
-                commands: d.commands.clone(),
-            }));
+            w.borrow_mut().push(Widget::Canvas(widget));
         });
 
         Ok(Value::Null)
     }
 );
+
+// canvas.render_rect(rect_id): renders into a rect from Tui.split_row/split_col instead of
+// the canvas' own position
+native_fn_with_data!(
+    CanvasRenderRectMethod,
+    "render_rect",
+    1,
+    CanvasData,
+    |_evaluator, args, cursor, data| {
+        let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
+        let widget = data.borrow().widget(Some(rect_id));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::Canvas(widget));
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use ratatui::style::Color;
+
+    use super::{CanvasCommand, CanvasData, CanvasTextMethod};
+    use crate::{
+        evaluator::{
+            Callable, Evaluator,
+            natives::tui::{WIDGETS, Widget},
+            runtime_err::ErrKind,
+            tests::{assert_err_kind, empty_src, global, run},
+            value::Value,
+        },
+        lexer::cursor::Cursor,
+    };
+
+    #[test]
+    fn text_command() {
+        let data = Rc::new(RefCell::new(CanvasData {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+            x_bounds: (0.0, 100.0),
+            y_bounds: (0.0, 100.0),
+            commands: Vec::new(),
+        }));
+        let method = CanvasTextMethod { data: data.clone() };
+
+        let src = empty_src();
+        let mut evaluator = Evaluator::new(&src);
+
+        let args = vec![
+            Value::Num(10.0.into()),
+            Value::Num(10.0.into()),
+            Value::str("hi"),
+            Value::str("red"),
+        ];
+        assert!(method.call(&mut evaluator, args, Cursor::new()).is_ok());
+        match data.borrow().commands.as_slice() {
+            [CanvasCommand::Text { x, y, text, color }] => {
+                assert_eq!((*x, *y), (10.0, 10.0));
+                assert_eq!(text, "hi");
+                assert_eq!(*color, Color::Red);
+            }
+            _ => panic!("expected a single Text command"),
+        }
+
+        let args = vec![
+            Value::Num(10.0.into()),
+            Value::Num(10.0.into()),
+            Value::Num(1.0.into()),
+            Value::str("red"),
+        ];
+        assert!(method.call(&mut evaluator, args, Cursor::new()).is_err());
+    }
+
+    #[test]
+    fn polygon_command() {
+        let (result, _) = run("var c = Tui.create_canvas(0, 0, 10, 10)\n\
+             c.polygon([[0, 0], [10, 0], [5, 8]], \"green\")\n\
+             c.render()");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::Canvas(widget)) => match widget.commands.as_slice() {
+                [CanvasCommand::Polygon { points, color }] => {
+                    assert_eq!(points, &vec![(0.0, 0.0), (10.0, 0.0), (5.0, 8.0)]);
+                    assert_eq!(*color, Color::Green);
+                }
+                _ => panic!("expected a single Polygon command"),
+            },
+            _ => panic!("expected a Canvas widget"),
+        });
+
+        for points in ["[[0, 0]]", "[[0, 0], [1]]", "[[0, 0], \"a\"]"] {
+            let (result, _) = run(&format!(
+                "var c = Tui.create_canvas(0, 0, 10, 10)\nc.polygon({points}, \"red\")"
+            ));
+            assert!(result.is_err(), "{points} should be rejected");
+        }
+    }
+
+    #[test]
+    fn color_must_be_str() {
+        for call in [
+            "c.polygon([[0, 0], [1, 1]], 1)",
+            "c.text(0, 0, \"hi\", true)",
+        ] {
+            let (result, _) = run(&format!("var c = Tui.create_canvas(0, 0, 10, 10)\n{call}"));
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn set_bounds() {
+        let (result, _) =
+            run("var c = Tui.create_canvas(0, 0, 10, 10)\nc.set_bounds(-1, 1, 0, 50)");
+        assert!(result.is_ok());
+
+        for bounds in ["10, 0, 0, 10", "0, 10, 5, 5"] {
+            let (result, _) = run(&format!(
+                "var c = Tui.create_canvas(0, 0, 10, 10)\nc.set_bounds({bounds})"
+            ));
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+
+    #[test]
+    fn render_into_rect() {
+        let id = global(
+            "var c = Tui.create_canvas(1, 2, 10, 10)\n\
+             var id = Tui.split_row(0, [50, 50])[1]\n\
+             c.render_rect(id)",
+            "id",
+        );
+        let Value::Num(id) = id else {
+            panic!("expected a Num rect id");
+        };
+
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::Canvas(widget)) => assert_eq!(widget.rect_id, Some(id.0 as usize)),
+            _ => panic!("expected a Canvas widget"),
+        });
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{
+    evaluator::natives::tui::{WIDGETS, Widget, canvas::points_from_value, parse_color},
+    native_fn, native_fn_with_data,
+};
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::evaluator::{
+    Callable, EvalResult, Evaluator,
+    object::{Method, NativeMethod, Object},
+    runtime_err::{ErrKind, RuntimeEvent},
+    value::Value,
+};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType},
+};
+
+// Tui.create_chart(x, y, width, height) -> Chart object
+native_fn!(
+    FnTuiCreateChart,
+    "tui_create_chart",
+    4,
+    |_evaluator, args, cursor| {
+        let x = args[0].check_num(cursor, Some("x position".into()))? as u16;
+        let y = args[1].check_num(cursor, Some("y position".into()))? as u16;
+        let width = args[2].check_num(cursor, Some("width".into()))? as u16;
+        let height = args[3].check_num(cursor, Some("height".into()))? as u16;
+
+        let chart_data = Rc::new(RefCell::new(ChartData {
+            x,
+            y,
+            width,
+            height,
+            bounds: None,
+            datasets: Vec::new(),
+        }));
+
+        let mut methods: HashMap<String, Method> = HashMap::new();
+
+        methods.insert(
+            "add_dataset".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(ChartAddDatasetMethod {
+                    data: Rc::clone(&chart_data),
+                }),
+                false,
+            )),
+        );
+
+        methods.insert(
+            "set_bounds".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(ChartSetBoundsMethod {
+                    data: Rc::clone(&chart_data),
+                }),
+                false,
+            )),
+        );
+
+        methods.insert(
+            "clear".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(ChartClearMethod {
+                    data: Rc::clone(&chart_data),
+                }),
+                false,
+            )),
+        );
+
+        methods.insert(
+            "render".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(ChartRenderMethod {
+                    data: Rc::clone(&chart_data),
+                }),
+                false,
+            )),
+        );
+
+        methods.insert(
+            "render_rect".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(ChartRenderRectMethod {
+                    data: Rc::clone(&chart_data),
+                }),
+                false,
+            )),
+        );
+
+        Ok(Value::Obj(Rc::new(Object::new("Chart".into(), methods))))
+    }
+);
+
+pub struct ChartData {
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    // x and y bounds set with set_bounds, computed from the datasets when None
+    bounds: Option<((f64, f64), (f64, f64))>,
+    datasets: Vec<ChartDataset>,
+}
+
+#[derive(Clone)]
+pub struct ChartDataset {
+    pub name: String,
+    pub points: Vec<(f64, f64)>,
+    pub color: Color,
+}
+
+#[derive(Clone)]
+pub struct ChartWidget {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub x_bounds: (f64, f64),
+    pub y_bounds: (f64, f64),
+    pub datasets: Vec<ChartDataset>,
+    // Layout rect to render into, overrides the absolute position when set
+    pub rect_id: Option<usize>,
+}
+
+impl ChartData {
+    fn widget(&self, rect_id: Option<usize>) -> ChartWidget {
+        let (x_bounds, y_bounds) = self.bounds.unwrap_or_else(|| {
+            let points = self.datasets.iter().flat_map(|d| d.points.iter());
+            (
+                data_bounds(points.clone().map(|p| p.0)),
+                data_bounds(points.map(|p| p.1)),
+            )
+        });
+        ChartWidget {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            x_bounds,
+            y_bounds,
+            datasets: self.datasets.clone(),
+            rect_id,
+        }
+    }
+}
+
+/// Smallest range containing all `values`, widened so it's never empty
+fn data_bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+    if min > max {
+        (0.0, 1.0)
+    } else if min == max {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+pub fn render_chart(frame: &mut Frame<'_>, widget: &ChartWidget, area: Rect) {
+    let datasets = widget
+        .datasets
+        .iter()
+        .map(|d| {
+            Dataset::default()
+                .name(d.name.clone())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(d.color))
+                .data(&d.points)
+        })
+        .collect();
+    let axis = |(min, max): (f64, f64)| {
+        Axis::default()
+            .bounds([min, max])
+            .labels([min.to_string(), max.to_string()])
+    };
+    let chart = Chart::new(datasets)
+        .x_axis(axis(widget.x_bounds))
+        .y_axis(axis(widget.y_bounds));
+    frame.render_widget(chart, area);
+}
+
+// Chart method implementations using the macro
+
+// chart.add_dataset(name, points, color): points is a List of [x, y] pairs drawn as a line
+native_fn_with_data!(
+    ChartAddDatasetMethod,
+    "add_dataset",
+    3,
+    ChartData,
+    |_evaluator, args, cursor, data| {
+        let name = args[0].check_str(cursor, Some("dataset name".into()))?;
+        let points = points_from_value(&args[1], "dataset", cursor)?;
+        let color = parse_color(&args[2].check_str(cursor, Some("color".into()))?.borrow());
+
+        data.borrow_mut().datasets.push(ChartDataset {
+            name: name.borrow().clone(),
+            points,
+            color,
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+native_fn_with_data!(
+    ChartSetBoundsMethod,
+    "set_bounds",
+    4,
+    ChartData,
+    |_evaluator, args, cursor, data| {
+        let x_min = args[0].check_num(cursor, Some("min x".into()))?;
+        let x_max = args[1].check_num(cursor, Some("max x".into()))?;
+        let y_min = args[2].check_num(cursor, Some("min y".into()))?;
+        let y_max = args[3].check_num(cursor, Some("max y".into()))?;
+
+        if x_max <= x_min || y_max <= y_min {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!(
+                    "invalid chart bounds x: [{}, {}], y: [{}, {}], max must be greater than min",
+                    x_min, x_max, y_min, y_max
+                ),
+                cursor,
+            ));
+        }
+
+        data.borrow_mut().bounds = Some(((x_min, x_max), (y_min, y_max)));
+
+        Ok(Value::Null)
+    }
+);
+
+// chart.clear(): removes all datasets, bounds set with set_bounds are kept
+native_fn_with_data!(
+    ChartClearMethod,
+    "clear",
+    0,
+    ChartData,
+    |_evaluator, _args, _cursor, data| {
+        data.borrow_mut().datasets.clear();
+        Ok(Value::Null)
+    }
+);
+
+native_fn_with_data!(
+    ChartRenderMethod,
+    "render",
+    0,
+    ChartData,
+    |_evaluator, _args, _cursor, data| {
+        let widget = data.borrow().widget(None);
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::Chart(widget));
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// chart.render_rect(rect_id): renders into a rect from Tui.split_row/split_col instead of
+// the chart's own position
+native_fn_with_data!(
+    ChartRenderRectMethod,
+    "render_rect",
+    1,
+    ChartData,
+    |_evaluator, args, cursor, data| {
+        let rect_id = args[0].check_num(cursor, Some("rect id".into()))? as usize;
+        let widget = data.borrow().widget(Some(rect_id));
+
+        WIDGETS.with(|w| {
+            w.borrow_mut().push(Widget::Chart(widget));
+        });
+
+        Ok(Value::Null)
+    }
+);
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use crate::evaluator::{
+        natives::tui::{WIDGETS, Widget},
+        runtime_err::ErrKind,
+        tests::{assert_err_kind, run},
+    };
+
+    #[test]
+    fn render_datasets() {
+        let (result, _) = run("var c = Tui.create_chart(0, 0, 40, 10)\n\
+             c.add_dataset(\"a\", [[0, 1], [1, 3], [2, 2]], \"red\")\n\
+             c.add_dataset(\"b\", [[-1, 5]], \"blue\")\n\
+             c.render()");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::Chart(widget)) => {
+                assert_eq!(widget.datasets.len(), 2);
+                assert_eq!(widget.datasets[0].color, Color::Red);
+                // bounds are computed from all the datasets
+                assert_eq!(widget.x_bounds, (-1.0, 2.0));
+                assert_eq!(widget.y_bounds, (1.0, 5.0));
+            }
+            _ => panic!("expected a Chart widget"),
+        });
+
+        let (result, _) = run("var c = Tui.create_chart(0, 0, 40, 10)\n\
+             c.add_dataset(\"a\", [[0, 1]], \"red\")\n\
+             c.set_bounds(0, 10, -5, 5)\n\
+             c.render()");
+        assert!(result.is_ok());
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::Chart(widget)) => {
+                assert_eq!(
+                    (widget.x_bounds, widget.y_bounds),
+                    ((0.0, 10.0), (-5.0, 5.0))
+                );
+            }
+            _ => panic!("expected a Chart widget"),
+        });
+
+        let (result, _) =
+            run("var c = Tui.create_chart(0, 0, 40, 10)\nc.add_dataset(\"a\", [[0]], \"red\")");
+        assert_err_kind(result, ErrKind::Value);
+
+        let (result, _) =
+            run("var c = Tui.create_chart(0, 0, 40, 10)\nc.add_dataset(\"a\", [[0, 1]], 1)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    evaluator::{
+        natives::term::key_input,
+        object::{Method, NativeMethod, Object},
+    },
+    native_fn, native_fn_with_val,
+};
+
+use std::{collections::HashMap, io, rc::Rc, time::Duration};
+
+use crate::evaluator::{
+    Callable, EvalResult, Evaluator,
+    runtime_err::{ErrKind, RuntimeEvent},
+    value::Value,
+};
+use crossterm::{
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    execute,
+};
+use ordered_float::OrderedFloat;
+
+// Tui.enable_mouse(): starts reporting mouse events to Tui.poll_event()
+native_fn!(
+    FnTuiEnableMouse,
+    "tui_enable_mouse",
+    0,
+    |_evaluator, _args, _cursor| {
+        execute!(io::stdout(), EnableMouseCapture)?;
+        Ok(Value::Null)
+    }
+);
+
+// Tui.disable_mouse(): stops reporting mouse events
+native_fn!(
+    FnTuiDisableMouse,
+    "tui_disable_mouse",
+    0,
+    |_evaluator, _args, _cursor| {
+        execute!(io::stdout(), DisableMouseCapture)?;
+        Ok(Value::Null)
+    }
+);
+
+// Tui.poll_event(timeout_ms) -> KeyInput, MouseInput or Null if nothing happened in time
+native_fn!(
+    FnTuiPollEvent,
+    "tui_poll_event",
+    1,
+    |_evaluator, args, cursor| {
+        let timeout = args[0].check_num(cursor, Some("timeout".into()))?;
+        let timeout = Duration::try_from_secs_f64(timeout.max(0.0) / 1000.0).map_err(|_| {
+            RuntimeEvent::error(
+                ErrKind::Value,
+                format!("invalid poll timeout {}", timeout),
+                cursor,
+            )
+        })?;
+
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key_event) => return Ok(key_input(key_event)),
+                Event::Mouse(mouse_event) => {
+                    let size = crossterm::terminal::size()?;
+                    return Ok(mouse_input(mouse_event, size).unwrap_or(Value::Null));
+                }
+                _ => {}
+            }
+        }
+        Ok(Value::Null)
+    }
+);
+
+// Build a MouseInput object with kind(), button(), x() and y() getters, returns None for
+// plain mouse movement and events outside the terminal area
+fn mouse_input(mouse_event: MouseEvent, size: (u16, u16)) -> Option<Value> {
+    let (cols, rows) = size;
+    if mouse_event.column >= cols || mouse_event.row >= rows {
+        return None;
+    }
+
+    let (kind, button) = match mouse_event.kind {
+        MouseEventKind::Down(button) => ("down", Some(button)),
+        MouseEventKind::Up(button) => ("up", Some(button)),
+        MouseEventKind::Drag(button) => ("drag", Some(button)),
+        MouseEventKind::ScrollUp => ("scroll_up", None),
+        MouseEventKind::ScrollDown => ("scroll_down", None),
+        _ => return None,
+    };
+    let button = match button {
+        Some(MouseButton::Left) => Value::str("left"),
+        Some(MouseButton::Right) => Value::str("right"),
+        Some(MouseButton::Middle) => Value::str("middle"),
+        None => Value::Null,
+    };
+
+    let mut methods: HashMap<String, Method> = HashMap::new();
+    for (name, val) in [
+        ("kind", Value::str(kind)),
+        ("button", button),
+        ("x", Value::Num(OrderedFloat(mouse_event.column as f64))),
+        ("y", Value::Num(OrderedFloat(mouse_event.row as f64))),
+    ] {
+        methods.insert(
+            name.into(),
+            Method::Native(NativeMethod::new(Rc::new(MouseInputGetter { val }), false)),
+        );
+    }
+
+    Some(Value::Obj(Rc::new(Object::new(
+        "MouseInput".into(),
+        methods,
+    ))))
+}
+
+native_fn_with_val!(
+    MouseInputGetter,
+    "mouse_input_getter",
+    0,
+    Value,
+    |_evaluator, _args, _cursor, val| { Ok(val.clone()) }
+);
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use super::mouse_input;
+    use crate::{
+        evaluator::{
+            Evaluator,
+            runtime_err::ErrKind,
+            tests::{assert_err_kind, empty_src, run},
+            value::Value,
+        },
+        lexer::cursor::Cursor,
+    };
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn mouse_click() {
+        let src = empty_src();
+        let mut evaluator = Evaluator::new(&src);
+
+        let event = mouse(MouseEventKind::Down(MouseButton::Left), 3, 4);
+        let Some(Value::Obj(obj)) = mouse_input(event, (80, 24)) else {
+            panic!("expected a MouseInput object");
+        };
+        assert_eq!(obj.name, "MouseInput");
+
+        let mut get = |name: &str| {
+            let method = obj.methods.get(name).expect("missing getter");
+            method
+                .get_callable()
+                .call(&mut evaluator, vec![], Cursor::new())
+                .expect("getter failed")
+                .to_string()
+        };
+        assert_eq!(get("kind"), "down");
+        assert_eq!(get("button"), "left");
+        assert_eq!((get("x"), get("y")), ("3".into(), "4".into()));
+
+        // outside the terminal area and plain movement are ignored
+        let event = mouse(MouseEventKind::Down(MouseButton::Left), 80, 4);
+        assert!(mouse_input(event, (80, 24)).is_none());
+        let event = mouse(MouseEventKind::Moved, 1, 1);
+        assert!(mouse_input(event, (80, 24)).is_none());
+    }
+
+    #[test]
+    fn invalid_poll_timeout() {
+        for src in ["Tui.poll_event(1 / 0)", "Tui.poll_event(10 ** 300)"] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+}
@@ -8,7 +8,11 @@ use crate::{
 
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::evaluator::{Callable, EvalResult, Evaluator, value::Value};
+use crate::evaluator::{
+    Callable, EvalResult, Evaluator,
+    runtime_err::{ErrKind, RuntimeEvent},
+    value::Value,
+};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -33,6 +37,8 @@ native_fn!(
             content: String::new(),
             cursor: 0,
             placeholder,
+            max_length: 0,
+            mask: None,
             focused: false,
             style: TuiStyle::default(),
         }));
@@ -79,6 +85,26 @@ native_fn!(
             )),
         );
 
+        methods.insert(
+            "set_max_length".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(TextInputSetMaxLengthMethod {
+                    data: Rc::clone(&input_data),
+                }),
+                false,
+            )),
+        );
+
+        methods.insert(
+            "set_mask".into(),
+            Method::Native(NativeMethod::new(
+                Rc::new(TextInputSetMaskMethod {
+                    data: Rc::clone(&input_data),
+                }),
+                false,
+            )),
+        );
+
         methods.insert(
             "set_focused".into(),
             Method::Native(NativeMethod::new(
@@ -131,10 +157,33 @@ pub struct TextInputData {
     content: String,
     cursor: usize,
     placeholder: String,
+    // Maximum number of chars in content, 0 means unlimited
+    max_length: usize,
+    // Char shown in place of each content char, for passwords
+    mask: Option<char>,
     focused: bool,
     style: TuiStyle,
 }
 
+impl TextInputData {
+    /// Number of chars that can still be added before reaching max_length
+    fn room(&self) -> usize {
+        if self.max_length == 0 {
+            usize::MAX
+        } else {
+            self.max_length.saturating_sub(self.content.chars().count())
+        }
+    }
+
+    /// Cut content down to max_length, keeping the cursor inside it
+    fn truncate(&mut self) {
+        if self.max_length > 0 {
+            self.content = self.content.chars().take(self.max_length).collect();
+        }
+        self.cursor = self.cursor.min(self.content.chars().count());
+    }
+}
+
 // Method implementations using the macro
 
 native_fn_with_data!(
@@ -162,6 +211,7 @@ native_fn_with_data!(
         let mut d = data.borrow_mut();
         d.content = text;
         d.cursor = d.content.chars().count();
+        d.truncate();
 
         Ok(Value::Null)
     }
@@ -191,8 +241,12 @@ native_fn_with_data!(
                 }
             }
             "Space" => {
-                d.content.insert(cursor, ' ');
-                d.cursor += 1;
+                if d.room() > 0 {
+                    let mut chars: Vec<char> = d.content.chars().collect();
+                    chars.insert(cursor, ' ');
+                    d.content = chars.into_iter().collect();
+                    d.cursor += 1;
+                }
             }
             "Delete" => {
                 let char_count = d.content.chars().count();
@@ -220,11 +274,11 @@ native_fn_with_data!(
             }
             // Don't process special keys
             "Shift" | "Up" | "Down" | "Enter" | "Esc" | "Tab" | "PageUp" | "PageDown" => {}
-            // Everything else is a printable character
+            // Everything else is a printable character, dropped once max_length is reached
             _ => {
                 let mut chars: Vec<char> = d.content.chars().collect();
-                for c in key.chars() {
-                    chars.insert(cursor, c);
+                for c in key.chars().take(d.room()) {
+                    chars.insert(d.cursor, c);
                     d.cursor += 1;
                 }
                 d.content = chars.into_iter().collect();
@@ -248,6 +302,65 @@ native_fn_with_data!(
     }
 );
 
+// input.set_max_length(n): caps the content at n chars, 0 removes the limit
+native_fn_with_data!(
+    TextInputSetMaxLengthMethod,
+    "set_max_length",
+    1,
+    TextInputData,
+    |_evaluator, args, cursor, data| {
+        let max_length = args[0].check_num(cursor, Some("max length".into()))?;
+        if max_length < 0.0 || max_length.fract() != 0.0 {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!(
+                    "max length must be a whole number >= 0, found {}",
+                    max_length
+                ),
+                cursor,
+            ));
+        }
+
+        let mut d = data.borrow_mut();
+        d.max_length = max_length as usize;
+        d.truncate();
+
+        Ok(Value::Null)
+    }
+);
+
+// input.set_mask(char): shows content as char repeated, Null or "" shows it as is again
+native_fn_with_data!(
+    TextInputSetMaskMethod,
+    "set_mask",
+    1,
+    TextInputData,
+    |_evaluator, args, cursor, data| {
+        let mask = match &args[0] {
+            Value::Null => None,
+            val => {
+                let mask = val.check_str(cursor, Some("mask".into()))?;
+                let mask = mask.borrow();
+                let mut chars = mask.chars();
+                match (chars.next(), chars.next()) {
+                    (None, _) => None,
+                    (Some(c), None) => Some(c),
+                    _ => {
+                        return Err(RuntimeEvent::error(
+                            ErrKind::Value,
+                            format!("mask must be a single char, found \"{}\"", mask),
+                            cursor,
+                        ));
+                    }
+                }
+            }
+        };
+
+        data.borrow_mut().mask = mask;
+        Ok(Value::Null)
+    }
+);
+
 native_fn_with_data!(
     TextInputSetFocusedMethod,
     "set_focused",
@@ -294,6 +407,7 @@ native_fn_with_data!(
                 content: d.content.clone(),
                 cursor: d.cursor,
                 placeholder: d.placeholder.clone(),
+                mask: d.mask,
                 focused: d.focused,
                 style: d.style.clone(),
             }));
@@ -311,49 +425,186 @@ pub struct TextInputWidget {
     pub content: String,
     pub cursor: usize,
     pub placeholder: String,
+    pub mask: Option<char>,
     pub focused: bool,
     pub style: TuiStyle,
 }
 
 pub fn render_text_input(frame: &mut Frame<'_>, widget: &TextInputWidget, area: Rect) {
+    let display_with_cursor = visible_text(widget);
+
+    let paragraph = Paragraph::new(display_with_cursor)
+        .style(widget.style.text_style())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(widget.style.border_style(widget.focused)),
+        );
+
+    frame.render_widget(paragraph, area);
+}
+
+/// The text shown inside the input's borders, scrolled horizontally so the cursor stays
+/// within the widget's width
+fn visible_text(widget: &TextInputWidget) -> String {
     let display_text = if widget.content.is_empty() {
         if widget.focused {
             String::new()
         } else {
             widget.placeholder.clone()
         }
+    } else if let Some(mask) = widget.mask {
+        widget.content.chars().map(|_| mask).collect()
     } else {
         widget.content.clone()
     };
+    let chars: Vec<char> = display_text.chars().collect();
 
+    // the cursor marker takes up a column of its own when focused
     let inner_width = widget.width.saturating_sub(2) as usize;
-    let chars: Vec<char> = display_text.chars().collect();
-    let scroll_offset = if widget.cursor > inner_width {
-        widget.cursor.saturating_sub(inner_width)
-    } else {
-        0
-    };
-    let visible_end = (scroll_offset + inner_width).min(chars.len());
-    let visible_text: String = chars[scroll_offset..visible_end].iter().collect();
-
-    let display_with_cursor = if widget.focused {
-        let cursor_pos = widget.cursor.saturating_sub(scroll_offset);
-        let mut chars: Vec<char> = visible_text.chars().collect();
-        if cursor_pos <= chars.len() {
-            chars.insert(cursor_pos, '│');
-        }
-        chars.iter().collect()
-    } else {
-        visible_text
+    let text_width = inner_width.saturating_sub(widget.focused as usize);
+    let cursor = widget.cursor.min(chars.len());
+    let scroll_offset = cursor.saturating_sub(text_width);
+    let visible_end = (scroll_offset + text_width).min(chars.len());
+    let mut visible: Vec<char> = chars[scroll_offset..visible_end].to_vec();
+
+    if widget.focused {
+        visible.insert(cursor - scroll_offset, '│');
+    }
+    visible.into_iter().collect()
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::{TextInputWidget, visible_text};
+    use crate::evaluator::{
+        natives::tui::{TuiStyle, WIDGETS, Widget},
+        runtime_err::ErrKind,
+        tests::{assert_err_kind, global, run},
     };
 
-    let paragraph = Paragraph::new(display_with_cursor)
-        .style(widget.style.text_style())
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(widget.style.border_style(widget.focused)),
+    fn rendered_input() -> TextInputWidget {
+        WIDGETS.with(|w| match w.borrow().last() {
+            Some(Widget::TextInput(widget)) => widget.clone(),
+            _ => panic!("expected a TextInput widget"),
+        })
+    }
+
+    #[test]
+    fn max_length() {
+        let text = global(
+            "var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_max_length(3)\n\
+             for key in [\"a\", \"b\", \"c\", \"d\", \"Space\"] do\n\
+                 t.handle_key(key)\n\
+             end\n\
+             var text = t.get_text()",
+            "text",
+        );
+        assert_eq!(text.to_string(), "abc");
+
+        // the cursor still moves at the limit and freed up room can be typed into again
+        let text = global(
+            "var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_text(\"abcdef\")\n\
+             t.set_max_length(3)\n\
+             for key in [\"Right\", \"Left\", \"x\", \"Backspace\", \"yz\"] do\n\
+                 t.handle_key(key)\n\
+             end\n\
+             t.render()\n\
+             var text = t.get_text()",
+            "text",
         );
+        assert_eq!(text.to_string(), "ayc");
+        assert_eq!(rendered_input().cursor, 2);
+
+        let text = global(
+            "var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_max_length(0)\n\
+             t.handle_key(\"hello\")\n\
+             var text = t.get_text()",
+            "text",
+        );
+        assert_eq!(text.to_string(), "hello");
+    }
 
-    frame.render_widget(paragraph, area);
+    #[test]
+    fn scrolls_to_cursor() {
+        let mut widget = TextInputWidget {
+            x: 0,
+            y: 0,
+            width: 7,
+            content: "abcdefghij".into(),
+            cursor: 10,
+            placeholder: String::new(),
+            mask: None,
+            focused: true,
+            style: TuiStyle::default(),
+        };
+        // 5 columns inside the borders, one of them used by the cursor
+        assert_eq!(visible_text(&widget), "ghij│");
+        widget.cursor = 2;
+        assert_eq!(visible_text(&widget), "ab│cd");
+        widget.focused = false;
+        assert_eq!(visible_text(&widget), "abcde");
+    }
+
+    #[test]
+    fn cursor_across_focus_and_set_text() {
+        let (result, _) = run("var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_focused(true)\n\
+             t.set_text(\"hello\")\n\
+             t.handle_key(\"Left\")\n\
+             t.set_focused(false)\n\
+             t.render()\n\
+             t.set_focused(true)\n\
+             t.render()");
+        assert!(result.is_ok());
+        let (unfocused, focused) = WIDGETS.with(|w| {
+            let w = w.borrow();
+            match &w[w.len() - 2..] {
+                [Widget::TextInput(a), Widget::TextInput(b)] => (a.clone(), b.clone()),
+                _ => panic!("expected two TextInput widgets"),
+            }
+        });
+        assert_eq!(visible_text(&unfocused), "hello");
+        // the cursor is kept while unfocused
+        assert_eq!(visible_text(&focused), "hell│o");
+
+        let (result, _) = run("var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_text(\"a longer text\")\n\
+             t.set_text(\"ab\")\n\
+             t.render()");
+        assert!(result.is_ok());
+        assert_eq!(rendered_input().cursor, 2);
+    }
+
+    #[test]
+    fn masked_input() {
+        let text = global(
+            "var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_text(\"secret\")\n\
+             t.set_mask(\"*\")\n\
+             t.render()\n\
+             var text = t.get_text()",
+            "text",
+        );
+        assert_eq!(text.to_string(), "secret");
+        assert_eq!(visible_text(&rendered_input()), "******");
+
+        let text = global(
+            "var t = Tui.create_text_input(0, 0, 20, \"\")\n\
+             t.set_text(\"secret\")\n\
+             t.set_mask(\"*\")\n\
+             t.set_mask(Null)\n\
+             t.render()\n\
+             var text = t.get_text()",
+            "text",
+        );
+        assert_eq!(visible_text(&rendered_input()), text.to_string());
+
+        let (result, _) = run("var t = Tui.create_text_input(0, 0, 20, \"\")\nt.set_mask(\"**\")");
+        assert_err_kind(result, ErrKind::Value);
+    }
 }
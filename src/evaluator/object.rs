@@ -43,6 +43,10 @@ impl Callable for NativeMethod {
         self.callable.arity()
     }
 
+    fn min_arity(&self) -> usize {
+        self.callable.min_arity()
+    }
+
     fn call(
         &self,
         evaluator: &mut crate::evaluator::Evaluator,
@@ -101,11 +105,22 @@ impl Method {
 pub struct Object {
     pub name: String,
     pub methods: HashMap<String, Method>,
+    /// Static constant properties (such as `Math.PI`)
+    pub consts: HashMap<String, Value>,
 }
 
 impl Object {
     pub fn new(name: String, methods: HashMap<String, Method>) -> Self {
-        Self { name, methods }
+        Self {
+            name,
+            methods,
+            consts: HashMap::new(),
+        }
+    }
+
+    pub fn with_consts(mut self, consts: HashMap<String, Value>) -> Self {
+        self.consts = consts;
+        self
     }
 
     fn find_method(&self, name: String) -> Option<Method> {
@@ -129,6 +144,17 @@ impl Callable for Object {
         0
     }
 
+    fn min_arity(&self) -> usize {
+        if let Some(init) = self.find_method("init".to_string()) {
+            return match init {
+                Method::User(func) => func.min_arity(),
+                Method::Native(func) => func.min_arity(),
+            };
+        }
+
+        0
+    }
+
     fn call(
         &self,
         evaluator: &mut super::Evaluator,
@@ -177,16 +203,47 @@ impl Instance {
             return Ok(Value::Callable(bound.get_callable()));
         }
 
-        Err(RuntimeEvent::error(
+        Err(RuntimeEvent::error_with_note(
             ErrKind::Name,
             format!("undefined property '{}'", name),
+            inst_ref.available_props(),
             cursor,
         ))
     }
 
+    /// Lists the field and method names of the instance for undefined property errors
+    fn available_props(&self) -> String {
+        let mut fields: Vec<&String> = self.fields.keys().collect();
+        fields.sort();
+        let mut methods: Vec<&String> = self.obj.methods.keys().filter(|m| *m != "init").collect();
+        methods.sort();
+
+        let list = |names: Vec<&String>| {
+            if names.is_empty() {
+                "none".to_string()
+            } else {
+                names
+                    .iter()
+                    .map(|n| n.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            }
+        };
+        format!(
+            "{} has fields: {}; methods: {}",
+            self.obj.name,
+            list(fields),
+            list(methods)
+        )
+    }
+
     pub fn set(&mut self, name: String, val: Value) {
         self.fields.insert(name, val);
     }
+
+    pub fn fields(&self) -> &HashMap<String, Value> {
+        &self.fields
+    }
 }
 
 impl ToString for Instance {
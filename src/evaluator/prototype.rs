@@ -2,7 +2,7 @@ use ordered_float::OrderedFloat;
 
 use crate::native_fn;
 use colored::Colorize;
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, rc::Rc};
 
 use crate::{
     evaluator::{
@@ -15,6 +15,33 @@ use crate::{
 
 #[macro_export]
 macro_rules! proto_method {
+    (
+        $proto:ident,
+        $name:ident,
+        $str_name:expr,
+        $min_arity:literal..=$arity:literal,
+        |$evaluator:ident, $args:ident, $cursor:ident, $recv:ident| $body:block
+    ) => {
+        native_fn!(
+            $name,
+            $str_name,
+            $min_arity..=$arity,
+            |$evaluator, $args, $cursor| {
+                // receiver is always arg0
+                let $recv = $args.get(0).ok_or_else(|| {
+                    RuntimeEvent::error(
+                        ErrKind::Value,
+                        concat!($str_name, " called without receiver").into(),
+                        Cursor::new(),
+                    )
+                })?;
+
+                $body
+            }
+        );
+
+        $proto.add_method($str_name.to_string(), std::rc::Rc::new($name));
+    };
     (
         $proto:ident,
         $name:ident,
@@ -225,35 +252,37 @@ impl ValuePrototypes {
             }
         );
 
-        // insert(index, value): inserts value at index
+        // insert(index, value): inserts value at index, index can be equal to len() to append
         proto_method!(
             proto,
             ListInsert,
             "insert",
             2,
-            |_evaluator, args, _cursor, recv| {
+            |_evaluator, args, cursor, recv| {
                 if let Value::List(list) = recv {
-                    if let Value::Num(n) = args[1] {
-                        list.borrow_mut().insert(n.0 as usize, args[2].clone());
-                    }
+                    let len = list.borrow().len();
+                    let idx = match args[1] {
+                        Value::Num(n) if n.0 == len as f64 => len,
+                        _ => args[1].check_index(len, cursor, Some("list index".into()))?,
+                    };
+                    list.borrow_mut().insert(idx, args[2].clone());
                     return Ok(Value::Null);
                 }
                 unreachable!()
             }
         );
 
-        // remove(index): removes the element at index
+        // remove(index): removes the element at index and returns it
         proto_method!(
             proto,
             ListRemove,
             "remove",
             1,
-            |_evaluator, args, _cursor, recv| {
+            |_evaluator, args, cursor, recv| {
                 if let Value::List(list) = recv {
-                    if let Value::Num(n) = args[1] {
-                        list.borrow_mut().remove(n.0 as usize);
-                    }
-                    return Ok(Value::Null);
+                    let len = list.borrow().len();
+                    let idx = args[1].check_index(len, cursor, Some("list index".into()))?;
+                    return Ok(list.borrow_mut().remove(idx));
                 }
                 unreachable!()
             }
@@ -307,6 +336,190 @@ impl ValuePrototypes {
             }
         );
 
+        // index_of(val): returns the index of the first element equal to val, -1 if there's none
+        proto_method!(
+            proto,
+            ListIndexOf,
+            "index_of",
+            1,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::List(list) = recv {
+                    let idx = list
+                        .borrow()
+                        .iter()
+                        .position(|v| v.is_equal(&args[1]))
+                        .map_or(-1., |i| i as f64);
+                    return Ok(Value::Num(OrderedFloat(idx)));
+                }
+                unreachable!()
+            }
+        );
+
+        // slice(start, end): returns a new list of the elements in [start, end), bounds are
+        // clamped to the list
+        proto_method!(
+            proto,
+            ListSlice,
+            "slice",
+            2,
+            |_evaluator, args, cursor, recv| {
+                if let Value::List(list) = recv {
+                    let list = list.borrow();
+                    let start = slice_bound(&args[1], list.len(), cursor, "slice start")?;
+                    let end = slice_bound(&args[2], list.len(), cursor, "slice end")?;
+                    let items = if start < end {
+                        list[start..end].to_vec()
+                    } else {
+                        vec![]
+                    };
+                    return Ok(Value::List(Rc::new(RefCell::new(items))));
+                }
+                unreachable!()
+            }
+        );
+
+        // sort(less?): sorts the list in place, less(a, b) returns true if a goes before b
+        proto_method!(
+            proto,
+            ListSort,
+            "sort",
+            0..=1,
+            |evaluator, args, cursor, recv| {
+                if let Value::List(list) = recv {
+                    let items = list.borrow().clone();
+                    let sorted = match args.get(1) {
+                        Some(less_fn) => merge_sort(items, &mut |a, b| {
+                            evaluator
+                                .call_value(less_fn.clone(), vec![a.clone(), b.clone()], cursor)?
+                                .check_bool(cursor, Some("sort callback result".into()))
+                        })?,
+                        None => {
+                            // only lists of Nums, Strs or Bools have a natural order
+                            if let Some(first) = items.first() {
+                                let ty = first.get_type();
+                                if ty != "Num" && ty != "Str" && ty != "Bool" {
+                                    return Err(RuntimeEvent::error(
+                                        ErrKind::Type,
+                                        format!("cannot sort values of type {}", ty),
+                                        cursor,
+                                    ));
+                                }
+                                if let Some(other) = items.iter().find(|v| v.get_type() != ty) {
+                                    return Err(RuntimeEvent::error(
+                                        ErrKind::Type,
+                                        format!(
+                                            "cannot sort a list with values of type {} and {}",
+                                            ty,
+                                            other.get_type()
+                                        ),
+                                        cursor,
+                                    ));
+                                }
+                            }
+                            merge_sort(items, &mut |a, b| {
+                                Ok(a.compare(b, cursor)? == Some(Ordering::Less))
+                            })?
+                        }
+                    };
+                    *list.borrow_mut() = sorted;
+                    return Ok(Value::Null);
+                }
+                unreachable!()
+            }
+        );
+
+        // reverse(): reverses the list in place
+        proto_method!(
+            proto,
+            ListReverse,
+            "reverse",
+            0,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::List(list) = recv {
+                    list.borrow_mut().reverse();
+                    return Ok(Value::Null);
+                }
+                unreachable!()
+            }
+        );
+
+        // join(sep) -> Str: converts every element to an Str and joins them with sep between
+        proto_method!(
+            proto,
+            ListJoin,
+            "join",
+            1,
+            |_evaluator, args, cursor, recv| {
+                if let Value::List(list) = recv {
+                    let sep = args[1].check_str(cursor, Some("separator".to_string()))?;
+                    let joined = list
+                        .borrow()
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect::<Vec<_>>()
+                        .join(sep.borrow().as_str());
+                    return Ok(Value::Str(Rc::new(RefCell::new(joined))));
+                }
+                unreachable!()
+            }
+        );
+
+        // map(fn): returns a new list with fn(elem) called for every element
+        proto_method!(proto, ListMap, "map", 1, |evaluator, args, cursor, recv| {
+            if let Value::List(list) = recv {
+                // iterate over a snapshot so the callback can modify the list
+                let items = list.borrow().clone();
+                let mut mapped = Vec::with_capacity(items.len());
+                for item in items {
+                    mapped.push(evaluator.call_value(args[1].clone(), vec![item], cursor)?);
+                }
+                return Ok(Value::List(Rc::new(RefCell::new(mapped))));
+            }
+            unreachable!()
+        });
+
+        // filter(fn): returns a new list with the elements that fn(elem) returns true for
+        proto_method!(
+            proto,
+            ListFilter,
+            "filter",
+            1,
+            |evaluator, args, cursor, recv| {
+                if let Value::List(list) = recv {
+                    let items = list.borrow().clone();
+                    let mut filtered = vec![];
+                    for item in items {
+                        let keep =
+                            evaluator.call_value(args[1].clone(), vec![item.clone()], cursor)?;
+                        if keep.check_bool(cursor, Some("filter callback result".into()))? {
+                            filtered.push(item);
+                        }
+                    }
+                    return Ok(Value::List(Rc::new(RefCell::new(filtered))));
+                }
+                unreachable!()
+            }
+        );
+
+        // reduce(fn, init): folds the list into a single value with acc = fn(acc, elem)
+        proto_method!(
+            proto,
+            ListReduce,
+            "reduce",
+            2,
+            |evaluator, args, cursor, recv| {
+                if let Value::List(list) = recv {
+                    let items = list.borrow().clone();
+                    let mut acc = args[2].clone();
+                    for item in items {
+                        acc = evaluator.call_value(args[1].clone(), vec![acc, item], cursor)?;
+                    }
+                    return Ok(acc);
+                }
+                unreachable!()
+            }
+        );
+
         proto
     }
 
@@ -331,7 +544,7 @@ impl ValuePrototypes {
             }
         );
 
-        // len() -> Str: returns the length of the string
+        // len() -> Num: returns the length of the string in characters
         proto_method!(
             proto,
             StrLen,
@@ -339,7 +552,9 @@ impl ValuePrototypes {
             0,
             |_evaluator, args, _cursor, recv| {
                 if let Value::Str(str) = recv {
-                    return Ok(Value::Num(OrderedFloat(str.borrow().len() as f64)));
+                    return Ok(Value::Num(
+                        OrderedFloat(str.borrow().chars().count() as f64),
+                    ));
                 }
                 unreachable!()
             }
@@ -362,6 +577,264 @@ impl ValuePrototypes {
             }
         );
 
+        // upper() -> Str: returns an uppercase copy of the Str
+        proto_method!(
+            proto,
+            StrUpper,
+            "upper",
+            0,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::Str(str) = recv {
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        str.borrow().to_uppercase(),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // lower() -> Str: returns a lowercase copy of the Str
+        proto_method!(
+            proto,
+            StrLower,
+            "lower",
+            0,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::Str(str) = recv {
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        str.borrow().to_lowercase(),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // trim() -> Str: returns a copy of the Str without leading and trailing whitespace
+        proto_method!(
+            proto,
+            StrTrim,
+            "trim",
+            0,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::Str(str) = recv {
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        str.borrow().trim().to_string(),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // split(sep) -> List: splits the Str on every occurrence of sep
+        proto_method!(
+            proto,
+            StrSplit,
+            "split",
+            1,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let sep = args[1].check_str(cursor, Some("separator".to_string()))?;
+                    let sep = sep.borrow();
+                    if sep.is_empty() {
+                        return Err(RuntimeEvent::error(
+                            ErrKind::Value,
+                            "separator can't be an empty Str".into(),
+                            cursor,
+                        ));
+                    }
+                    let parts = str
+                        .borrow()
+                        .split(sep.as_str())
+                        .map(|part| Value::Str(Rc::new(RefCell::new(part.to_string()))))
+                        .collect();
+                    return Ok(Value::List(Rc::new(RefCell::new(parts))));
+                }
+                unreachable!()
+            }
+        );
+
+        // replace(from, to) -> Str: replaces every occurrence of from with to
+        proto_method!(
+            proto,
+            StrReplace,
+            "replace",
+            2,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let from = args[1].check_str(cursor, Some("pattern".to_string()))?;
+                    let to = args[2].check_str(cursor, Some("replacement".to_string()))?;
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        str.borrow()
+                            .replace(from.borrow().as_str(), to.borrow().as_str()),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // contains(sub) -> Bool: returns true if the Str contains sub
+        proto_method!(
+            proto,
+            StrContains,
+            "contains",
+            1,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let sub = args[1].check_str(cursor, Some("substring".to_string()))?;
+                    return Ok(Value::Bool(str.borrow().contains(sub.borrow().as_str())));
+                }
+                unreachable!()
+            }
+        );
+
+        // starts_with(prefix) -> Bool: returns true if the Str starts with prefix
+        proto_method!(
+            proto,
+            StrStartsWith,
+            "starts_with",
+            1,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let prefix = args[1].check_str(cursor, Some("prefix".to_string()))?;
+                    return Ok(Value::Bool(
+                        str.borrow().starts_with(prefix.borrow().as_str()),
+                    ));
+                }
+                unreachable!()
+            }
+        );
+
+        // ends_with(suffix) -> Bool: returns true if the Str ends with suffix
+        proto_method!(
+            proto,
+            StrEndsWith,
+            "ends_with",
+            1,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let suffix = args[1].check_str(cursor, Some("suffix".to_string()))?;
+                    return Ok(Value::Bool(
+                        str.borrow().ends_with(suffix.borrow().as_str()),
+                    ));
+                }
+                unreachable!()
+            }
+        );
+
+        // find(sub) -> Num: returns the character index of the first occurrence of sub, -1 if
+        // there's none
+        proto_method!(
+            proto,
+            StrFind,
+            "find",
+            1,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let sub = args[1].check_str(cursor, Some("substring".to_string()))?;
+                    let str = str.borrow();
+                    let idx = str
+                        .find(sub.borrow().as_str())
+                        .map_or(-1., |byte| str[..byte].chars().count() as f64);
+                    return Ok(Value::Num(OrderedFloat(idx)));
+                }
+                unreachable!()
+            }
+        );
+
+        // substr(start, len) -> Str: returns len characters starting at start, both are clamped
+        // to the Str
+        proto_method!(
+            proto,
+            StrSubstr,
+            "substr",
+            2,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let count = str.borrow().chars().count();
+                    let start = slice_bound(&args[1], count, cursor, "substr start")?;
+                    let len = slice_bound(&args[2], count - start, cursor, "substr length")?;
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        str.borrow().chars().skip(start).take(len).collect(),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // chars() -> List: returns the characters of the Str as single character Strs
+        proto_method!(
+            proto,
+            StrChars,
+            "chars",
+            0,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let chars = str
+                        .borrow()
+                        .chars()
+                        .map(|c| Value::Str(Rc::new(RefCell::new(c.to_string()))))
+                        .collect();
+                    return Ok(Value::List(Rc::new(RefCell::new(chars))));
+                }
+                unreachable!()
+            }
+        );
+
+        // pad_left(width, fill?) -> Str: pads the start of the Str with fill (" " by default) to
+        // be at least width characters long
+        proto_method!(
+            proto,
+            StrPadLeft,
+            "pad_left",
+            1..=2,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let padding = str_padding(&str.borrow(), &args, cursor)?;
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        padding + str.borrow().as_str(),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // pad_right(width, fill?) -> Str: pads the end of the Str with fill (" " by default) to
+        // be at least width characters long
+        proto_method!(
+            proto,
+            StrPadRight,
+            "pad_right",
+            1..=2,
+            |_evaluator, args, cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let padding = str_padding(&str.borrow(), &args, cursor)?;
+                    return Ok(Value::Str(Rc::new(RefCell::new(
+                        str.borrow().clone() + padding.as_str(),
+                    ))));
+                }
+                unreachable!()
+            }
+        );
+
+        // bytes() -> List: returns the UTF-8 bytes of the Str as Nums
+        proto_method!(
+            proto,
+            StrBytes,
+            "bytes",
+            0,
+            |_evaluator, args, _cursor, recv| {
+                if let Value::Str(str) = recv {
+                    let bytes = str
+                        .borrow()
+                        .bytes()
+                        .map(|b| Value::Num(OrderedFloat(b as f64)))
+                        .collect();
+                    return Ok(Value::List(Rc::new(RefCell::new(bytes))));
+                }
+                unreachable!()
+            }
+        );
+
         // Foreground colors
         str_color_method!(proto, StrBlack, "black", black);
         str_color_method!(proto, StrRed, "red", red);
@@ -606,7 +1079,7 @@ impl ValuePrototypes {
                             cursor,
                         )
                     })?;
-                    return Ok(dict.borrow_mut().remove(&key).unwrap_or(Value::Null));
+                    return Ok(dict.borrow_mut().shift_remove(&key).unwrap_or(Value::Null));
                 }
                 unreachable!()
             }
@@ -668,6 +1141,89 @@ impl ValuePrototypes {
     }
 }
 
+/// Widest Str pad_left/pad_right can produce, anything larger is almost certainly a mistake and
+/// could fail to allocate
+const MAX_PAD_WIDTH: f64 = 1_000_000.;
+
+/// Padding that makes `str` at least `width` characters long for `pad_left/pad_right(width, fill?)`
+fn str_padding(str: &str, args: &[Value], cursor: Cursor) -> EvalResult<String> {
+    let width = args[1].check_num(cursor, Some("width".into()))?;
+    if width.is_nan() || width > MAX_PAD_WIDTH {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!(
+                "pad width must be at most {}, found {}",
+                MAX_PAD_WIDTH, width
+            ),
+            cursor,
+        ));
+    }
+    let width = width.max(0.) as usize;
+    let fill = match args.get(2) {
+        Some(fill) => {
+            let fill = fill.check_str(cursor, Some("fill".into()))?;
+            let fill = fill.borrow();
+            let mut chars = fill.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => {
+                    return Err(RuntimeEvent::error(
+                        ErrKind::Value,
+                        format!("fill must be a single character, found \"{}\"", fill),
+                        cursor,
+                    ));
+                }
+            }
+        }
+        None => ' ',
+    };
+    let padding = width.saturating_sub(str.chars().count());
+    Ok(std::iter::repeat_n(fill, padding).collect())
+}
+
+/// Integer slice bound clamped to `0..=len`
+fn slice_bound(val: &Value, len: usize, cursor: Cursor, name: &str) -> EvalResult<usize> {
+    let n = val.check_num(cursor, Some(name.into()))?;
+    if n.fract() != 0. {
+        return Err(RuntimeEvent::error(
+            ErrKind::Value,
+            format!("{} must be an integer, found {}", name, n),
+            cursor,
+        ));
+    }
+    Ok(n.clamp(0., len as f64) as usize)
+}
+
+/// Stable merge sort with a fallible comparison, `less(a, b)` returns true if `a` goes before `b`.
+/// User comparators can be inconsistent, which `slice::sort_by` isn't guaranteed to tolerate
+fn merge_sort(
+    mut items: Vec<Value>,
+    less: &mut dyn FnMut(&Value, &Value) -> EvalResult<bool>,
+) -> EvalResult<Vec<Value>> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(items, less)?;
+    let right = merge_sort(right, less)?;
+
+    let mut out = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // take from the right only if strictly less so equal elements keep their order
+        if less(r, l)? {
+            out.extend(right.next());
+        } else {
+            out.extend(left.next());
+        }
+    }
+    out.extend(left);
+    out.extend(right);
+    Ok(out)
+}
+
 #[derive(Debug)]
 pub struct BoundMethod {
     pub receiver: Value,
@@ -684,6 +1240,10 @@ impl Callable for BoundMethod {
         self.method.arity()
     }
 
+    fn min_arity(&self) -> usize {
+        self.method.min_arity()
+    }
+
     fn call(
         &self,
         evaluator: &mut Evaluator,
@@ -697,3 +1257,224 @@ impl Callable for BoundMethod {
         self.method.call(evaluator, real_args, cursor)
     }
 }
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::{
+        runtime_err::{ErrKind, RuntimeEvent},
+        tests::{assert_err_kind, eval, global, run},
+    };
+
+    #[test]
+    fn list_push_pop() {
+        let src = "
+var list = [1, 2]
+list.push(3)
+var popped = list.pop()
+var empty = [].pop()
+";
+        assert_eq!(global(src, "popped").to_string(), "3");
+        assert_eq!(global(src, "list").to_string(), "[1, 2]");
+        assert_eq!(global(src, "empty").to_string(), "null");
+    }
+
+    #[test]
+    fn list_insert_remove() {
+        let src = "
+var list = [1, 2, 3, 4]
+var removed = list.remove(1)
+list.insert(0, 0)
+list.insert(list.len(), 5)
+";
+        assert_eq!(global(src, "removed").to_string(), "2");
+        assert_eq!(global(src, "list").to_string(), "[0, 1, 3, 4, 5]");
+    }
+
+    #[test]
+    fn list_index_out_of_bounds() {
+        for src in [
+            "[1].remove(1)",
+            "[1].remove(-1)",
+            "[1].insert(2, 0)",
+            "[1].remove(0.5)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+
+        match run("[1, 2].remove(5)").0 {
+            Err(RuntimeEvent::Err(err)) => {
+                assert_eq!(err.msg, "list index 5 out of bounds (len = 2)")
+            }
+            _ => panic!("expected a ValueErr"),
+        }
+    }
+
+    #[test]
+    fn list_search_and_slice() {
+        assert_eq!(eval("[1, 2, 3].contains(2)"), "true");
+        assert_eq!(eval("[[1], [2]].contains([2])"), "true");
+        assert_eq!(eval("[1, 2, 3].contains(4)"), "false");
+        assert_eq!(eval("[\"a\", \"b\", \"b\"].index_of(\"b\")"), "1");
+        assert_eq!(eval("[1, 2, 3].index_of(4)"), "-1");
+
+        assert_eq!(eval("[1, 2, 3, 4].slice(1, 3)"), "[2, 3]");
+        assert_eq!(eval("[1, 2, 3].slice(1, 10)"), "[2, 3]");
+        assert_eq!(eval("[1, 2, 3].slice(-5, 1)"), "[1]");
+        assert_eq!(eval("[1, 2, 3].slice(2, 1)"), "[]");
+        assert_err_kind(run("[1, 2].slice(0.5, 1)").0, ErrKind::Value);
+
+        // slices are copies
+        let src = "
+var list = [1, 2, 3]
+var part = list.slice(0, 2)
+part[0] = 10
+";
+        assert_eq!(global(src, "list").to_string(), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn list_sort_reverse() {
+        let src = "
+var nums = [3, 1, 2]
+nums.sort()
+var strs = [\"b\", \"c\", \"a\"]
+strs.sort()
+var desc = [3, 1, 2]
+desc.sort(fn(a, b) = a > b)
+var pairs = [[1, \"x\"], [0, \"y\"], [1, \"z\"]]
+pairs.sort(fn(a, b) = a[0] < b[0])
+var rev = [\"a\", \"b\"]
+rev.reverse()
+";
+        assert_eq!(global(src, "nums").to_string(), "[1, 2, 3]");
+        assert_eq!(global(src, "strs").to_string(), "[\"a\", \"b\", \"c\"]");
+        assert_eq!(global(src, "desc").to_string(), "[3, 2, 1]");
+        // sorting is stable
+        assert_eq!(
+            global(src, "pairs").to_string(),
+            "[[0, \"y\"], [1, \"x\"], [1, \"z\"]]"
+        );
+        assert_eq!(global(src, "rev").to_string(), "[\"b\", \"a\"]");
+        assert_eq!(eval("[].sort()"), "null");
+
+        for src in [
+            "[1, \"a\"].sort()",
+            "[[1], [2]].sort()",
+            "[2, 1].sort(fn(a, b) = 1)",
+        ] {
+            assert_err_kind(run(src).0, ErrKind::Type);
+        }
+    }
+
+    #[test]
+    fn list_map_filter_reduce() {
+        assert_eq!(eval("[1, 2, 3].map(fn(n) = n * 2)"), "[2, 4, 6]");
+        assert_eq!(eval("[1, 2, 3, 4].filter(fn(n) = n % 2 == 0)"), "[2, 4]");
+        assert_eq!(eval("[1, 2, 3].reduce(fn(acc, n) = acc + n, 0)"), "6");
+        assert_eq!(eval("[].map(fn(n) = n)"), "[]");
+        assert_eq!(eval("[1, 2].map(to_str)"), "[\"1\", \"2\"]");
+
+        for src in ["[1].map(fn(a, b) = a)", "[1].reduce(fn(n) = n, 0)"] {
+            assert_err_kind(run(src).0, ErrKind::Arity);
+        }
+        assert_err_kind(run("[1].filter(fn(n) = n)").0, ErrKind::Type);
+    }
+
+    #[test]
+    fn str_case_and_trim() {
+        assert_eq!(eval("\"abc\".upper()"), "ABC");
+        assert_eq!(eval("\"ÄBC\".lower()"), "äbc");
+        assert_eq!(eval("\"  hi \\t\".trim()"), "hi");
+    }
+
+    #[test]
+    fn str_split() {
+        assert_eq!(eval("\"a,b,c\".split(\",\").len()"), "3");
+        assert_eq!(eval("\"a,b,c\".split(\",\")"), "[\"a\", \"b\", \"c\"]");
+        assert_eq!(eval("\"a::b\".split(\"::\")[1]"), "b");
+    }
+
+    #[test]
+    fn list_join() {
+        assert_eq!(eval("[\"a\", \"b\", \"c\"].join(\"-\")"), "a-b-c");
+        assert_eq!(eval("[1, 2.5, true].join(\", \")"), "1, 2.5, true");
+        assert_eq!(eval("[].join(\"-\")"), "");
+        // join undoes split
+        assert_eq!(eval("\"a,b,,c\".split(\",\").join(\",\")"), "a,b,,c");
+
+        let (result, _) = run("[1, 2].join(0)");
+        assert_err_kind(result, ErrKind::Type);
+    }
+
+    #[test]
+    fn str_replace_and_contains() {
+        assert_eq!(eval("\"a-b-c\".replace(\"-\", \"+\")"), "a+b+c");
+        assert_eq!(eval("\"hello\".contains(\"ell\")"), "true");
+        assert_eq!(eval("\"hello\".contains(\"xyz\")"), "false");
+    }
+
+    #[test]
+    fn str_search_and_substr() {
+        assert_eq!(eval("\"hello\".starts_with(\"he\")"), "true");
+        assert_eq!(eval("\"hello\".starts_with(\"lo\")"), "false");
+        assert_eq!(eval("\"hello\".ends_with(\"lo\")"), "true");
+        assert_eq!(eval("\"hello\".ends_with(\"\")"), "true");
+
+        assert_eq!(eval("\"héllo\".find(\"l\")"), "2");
+        assert_eq!(eval("\"hello\".find(\"xyz\")"), "-1");
+
+        assert_eq!(eval("\"héllo\".substr(1, 3)"), "éll");
+        // past the end is clamped
+        assert_eq!(eval("\"hello\".substr(3, 10)"), "lo");
+        assert_eq!(eval("\"hello\".substr(10, 2)"), "");
+
+        for src in ["\"hello\".find(1)", "\"hello\".starts_with(Null)"] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+        let (result, _) = run("\"hello\".substr(0.5, 1)");
+        assert_err_kind(result, ErrKind::Value);
+    }
+
+    #[test]
+    fn str_chars_and_bytes() {
+        assert_eq!(eval("\"ab\".chars()"), "[\"a\", \"b\"]");
+        assert_eq!(eval("\"aé\".chars()"), "[\"a\", \"é\"]");
+        assert_eq!(eval("\"\".chars()"), "[]");
+        assert_eq!(eval("\"A\".bytes()"), "[65]");
+        assert_eq!(eval("\"é\".bytes()"), "[195, 169]");
+    }
+
+    #[test]
+    fn str_pad() {
+        assert_eq!(eval("\"7\".pad_left(3, \"0\")"), "007");
+        assert_eq!(eval("\"ab\".pad_right(4, \".\")"), "ab..");
+        assert_eq!(eval("\"ab\".pad_left(4)"), "  ab");
+        // a smaller width leaves the Str as is
+        assert_eq!(eval("\"hello\".pad_left(2, \"0\")"), "hello");
+        assert_eq!(eval("\"é\".pad_right(2, \"ü\")"), "éü");
+
+        for src in [
+            "\"a\".pad_left(3, \"ab\")",
+            "\"a\".pad_left(1000000000000000)",
+            "\"a\".pad_right(1 / 0)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Value);
+        }
+    }
+
+    #[test]
+    fn str_method_type_errs() {
+        for src in [
+            "\"a\".split(1)",
+            "\"a\".contains(Null)",
+            "\"a\".replace(\"a\", 1)",
+        ] {
+            let (result, _) = run(src);
+            assert_err_kind(result, ErrKind::Type);
+        }
+    }
+}
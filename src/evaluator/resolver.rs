@@ -87,6 +87,8 @@ pub struct Resolver<'a> {
     pub ast: Vec<Stmt>,
     /// Stack of lexical scopes
     scopes: Vec<HashMap<String, ScopedVar>>,
+    /// Number of loops enclosing the current statement (reset inside functions)
+    loop_depth: usize,
     /// Resolver output
     out: ResolverOutput,
 }
@@ -97,6 +99,7 @@ impl<'a> Resolver<'a> {
             src,
             ast: src.ast.clone().expect("expected ast"),
             scopes: vec![],
+            loop_depth: 0,
             out: ResolverOutput::default(),
         }
     }
@@ -133,8 +136,8 @@ impl<'a> Resolver<'a> {
             StmtKind::Throw(_) => self.resolve_stmt_err(stmt),
             StmtKind::Use(_) => self.resolve_stmt_use(stmt),
             StmtKind::Return(_) => self.resolve_stmt_return(stmt),
-            StmtKind::Break => Ok(()),
-            StmtKind::Continue => Ok(()),
+            StmtKind::Break => self.resolve_stmt_loop_ctrl(stmt, "break"),
+            StmtKind::Continue => self.resolve_stmt_loop_ctrl(stmt, "continue"),
             StmtKind::Var { .. } => self.resolve_stmt_var(stmt),
             StmtKind::Block(_) => self.resolve_stmt_block(stmt, false),
             StmtKind::If { .. } => self.resolve_stmt_if(stmt),
@@ -209,6 +212,16 @@ impl<'a> Resolver<'a> {
         unreachable!("Non-return statement passed to Resolver::resolve_stmt_return");
     }
 
+    fn resolve_stmt_loop_ctrl(&mut self, stmt: &Stmt, keyword: &str) -> ResolveResult {
+        if self.loop_depth == 0 {
+            return Err(ResolveErr::new(
+                format!("'{}' outside of a loop", keyword),
+                stmt.cursor,
+            ));
+        }
+        Ok(())
+    }
+
     fn resolve_stmt_if(&mut self, stmt: &Stmt) -> ResolveResult {
         if let StmtKind::If {
             condition,
@@ -270,7 +283,10 @@ impl<'a> Resolver<'a> {
             }
 
             // 5) resolve the body in that scope
-            self.resolve_stmt_block(body, true)?;
+            self.loop_depth += 1;
+            let result = self.resolve_stmt_block(body, true);
+            self.loop_depth -= 1;
+            result?;
 
             // 6) pop scope (will also warn on unused loop vars if you keep that)
             self.end_scope();
@@ -295,8 +311,10 @@ impl<'a> Resolver<'a> {
             if let Some(step_expr) = step {
                 self.resolve_expr(step_expr)?;
             }
-            self.resolve_stmt(body)?;
-            return Ok(());
+            self.loop_depth += 1;
+            let result = self.resolve_stmt(body);
+            self.loop_depth -= 1;
+            return result;
         }
         unreachable!("Non-while statement passed to Resolver::resolve_stmt_while");
     }
@@ -338,26 +356,49 @@ impl<'a> Resolver<'a> {
 
     fn resolve_stmt_fn(&mut self, stmt: &Stmt) -> ResolveResult {
         if let StmtKind::Fn {
-            name, params, body, ..
+            name,
+            params,
+            defaults,
+            body,
+            ..
         } = &stmt.kind
         {
             // Function name is bound in the enclosing scope.
             self.declare(name.clone(), stmt.cursor);
             self.define(name.clone(), stmt.cursor);
 
-            // Resolve function body in its own scope with parameters.
-            self.begin_scope();
-            for p in params {
-                self.declare(p.clone(), stmt.cursor);
-                self.define(p.clone(), stmt.cursor);
-            }
-            self.resolve_stmt_block(body, true)?;
-            self.end_scope();
-            return Ok(());
+            return self.resolve_fn_body(params, defaults, body, stmt.cursor);
         }
         unreachable!("Non-fn statement passed to Resolver::resolve_stmt_fn");
     }
 
+    fn resolve_fn_body(
+        &mut self,
+        params: &[String],
+        defaults: &[Expr],
+        body: &Stmt,
+        cursor: Cursor,
+    ) -> ResolveResult {
+        // Default values are evaluated in the scope the function is defined in
+        for default in defaults {
+            self.resolve_expr(default)?;
+        }
+
+        // Resolve function body in its own scope with parameters,
+        // loops outside of the function don't apply to its body.
+        self.begin_scope();
+        for p in params {
+            self.declare(p.clone(), cursor);
+            self.define(p.clone(), cursor);
+        }
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+        let result = self.resolve_stmt_block(body, true);
+        self.loop_depth = loop_depth;
+        result?;
+        self.end_scope();
+        Ok(())
+    }
+
     fn resolve_stmt_obj(&mut self, stmt: &Stmt) -> ResolveResult {
         if let StmtKind::Obj { name, methods } = &stmt.kind {
             self.declare(name.clone(), stmt.cursor);
@@ -412,7 +453,7 @@ impl<'a> Resolver<'a> {
                 Ok(())
             }
             ExprKind::Literal(_) => Ok(()),
-            ExprKind::List(list) => {
+            ExprKind::List(list) | ExprKind::Interp(list) => {
                 for expr in list {
                     self.resolve_expr(expr)?;
                 }
@@ -479,24 +520,37 @@ impl<'a> Resolver<'a> {
                 self.resolve_local(expr, KeywordKind::KSelf.to_string().as_str());
                 Ok(())
             }
+            ExprKind::Lambda {
+                params,
+                defaults,
+                body,
+            } => self.resolve_fn_body(params, defaults, body, expr.cursor),
         }
     }
 
     fn resolve_expr_var(&mut self, expr: &Expr, name: &str) -> ResolveResult {
         // If the variable exists in the innermost scope but is not yet defined,
         // we’re reading it in its own initializer.
-        if let Some(scope) = self.scopes.last_mut() {
-            if let Some(var) = scope.get_mut(name) {
-                if !var.defined {
-                    return Err(ResolveErr::new(
-                        "can't read local variable in its own initializer".into(),
-                        expr.cursor,
-                    ));
-                }
-                if !var.used {
-                    var.used = true;
-                }
-            }
+        if self
+            .scopes
+            .last()
+            .and_then(|scope| scope.get(name))
+            .is_some_and(|var| !var.defined)
+        {
+            return Err(ResolveErr::new(
+                "can't read local variable in its own initializer".into(),
+                expr.cursor,
+            ));
+        }
+
+        // Mark the closest declaration as used, it can be in any enclosing scope.
+        if let Some(var) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+        {
+            var.used = true;
         }
 
         // Annotate variable access distance if found; else it remains global (None).
@@ -546,3 +600,69 @@ impl<'a> Resolver<'a> {
         }
     }
 }
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::{lexer::Lexer, parser::Parser};
+
+    fn resolve(text: &str) -> ResolverOutput {
+        let mut src = Src::from_text(PathBuf::from("test.qte"), text.to_string());
+        src.tokens = Lexer::new(src.text.clone()).tokenize().tokens;
+        src.ast = Parser::new(&src).parse().ast;
+        assert!(src.ast.is_some(), "parser failed");
+        Resolver::new(&src).resolve()
+    }
+
+    fn resolve_errors(text: &str) -> Vec<String> {
+        resolve(text)
+            .errors
+            .unwrap_or_default()
+            .iter()
+            .map(|err| err.msg.clone())
+            .collect()
+    }
+
+    #[test]
+    fn loop_control_outside_loop() {
+        assert_eq!(resolve_errors("break"), vec!["'break' outside of a loop"]);
+        assert_eq!(
+            resolve_errors("if true do\n    continue\nend"),
+            vec!["'continue' outside of a loop"]
+        );
+        // a function body doesn't inherit the enclosing loop
+        assert_eq!(
+            resolve_errors("while true do\n    fn f() do\n        break\n    end\nend"),
+            vec!["'break' outside of a loop"]
+        );
+    }
+
+    #[test]
+    fn loop_control_inside_loop() {
+        assert!(resolve_errors("while true do\n    break\nend").is_empty());
+        assert!(
+            resolve_errors("for i in 0..3 do\n    if i do\n        continue\n    end\nend")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn locals_used_in_nested_blocks() {
+        let src = "
+fn find(list, target) do
+    var i = 0
+    while i < len(list) do
+        if list[i] == target do
+            return i
+        end
+        i += 1
+    end
+    return -1
+end";
+        assert_eq!(resolve(src).warning_count, 0);
+        assert_eq!(resolve("fn f(unused) do\nend").warning_count, 1);
+    }
+}
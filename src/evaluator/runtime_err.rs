@@ -11,6 +11,8 @@ pub enum RuntimeEvent {
     UserErr { val: Value, cursor: Cursor },
     Break,
     Continue,
+    // Raised by halt(), stops the program without an error
+    Halt,
 }
 
 impl RuntimeEvent {
@@ -45,6 +47,25 @@ impl RuntimeEvent {
     pub fn is_return(&self) -> bool {
         matches!(self, RuntimeEvent::Return(_))
     }
+
+    /// Point errors that were created without a location (eg. converted from an io::Error) at
+    /// `cursor` instead
+    pub fn or_cursor(mut self, cursor: Cursor) -> Self {
+        match &mut self {
+            RuntimeEvent::Err(err) if err.cursor == Cursor::new() => err.cursor = cursor,
+            RuntimeEvent::UserErr { cursor: c, .. } if *c == Cursor::new() => *c = cursor,
+            _ => {}
+        }
+        self
+    }
+}
+
+/// Run a native function body, errors without a location are reported at the call site
+pub fn at_call_site<F>(cursor: Cursor, body: F) -> EvalResult<Value>
+where
+    F: FnOnce() -> EvalResult<Value>,
+{
+    body().map_err(|err| err.or_cursor(cursor))
 }
 
 impl From<io::Error> for RuntimeEvent {
@@ -99,7 +120,7 @@ impl Display for RuntimeErr {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum ErrKind {
     Type,
     Name,
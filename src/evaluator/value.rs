@@ -1,11 +1,12 @@
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    cmp::Ordering,
     fmt::{Debug, Display},
     ops::Deref,
     rc::Rc,
 };
 
+use indexmap::IndexMap;
 use ordered_float::OrderedFloat;
 
 use crate::{
@@ -16,6 +17,7 @@ use crate::{
         runtime_err::{ErrKind, EvalResult, RuntimeErr, RuntimeEvent},
     },
     lexer::cursor::Cursor,
+    parser::expr::AssignOp,
 };
 
 #[derive(Debug, Clone)]
@@ -25,7 +27,7 @@ pub enum Value {
     Num(OrderedFloat<f64>),
     Str(Rc<RefCell<String>>),
     List(Rc<RefCell<Vec<Value>>>),
-    Dict(Rc<RefCell<HashMap<ValueKey, Value>>>),
+    Dict(Rc<RefCell<IndexMap<ValueKey, Value>>>),
     Callable(Rc<dyn Callable>),
     Obj(Rc<Object>),
     ObjInstance(Rc<RefCell<Instance>>),
@@ -37,12 +39,46 @@ impl PartialEq for Value {
     }
 }
 
+/// Format a Num for display, whole numbers print without a decimal point and other numbers
+/// are rounded to 15 significant digits to hide float noise (eg. `0.1 + 0.2` prints `0.3`).
+/// Magnitudes outside of `1e-7..1e21` use scientific notation (eg. `1e21`)
+pub fn format_num(n: f64) -> String {
+    if !n.is_finite() {
+        return n.to_string();
+    }
+    if n == 0.0 {
+        // also covers -0
+        return "0".into();
+    }
+
+    let abs = n.abs();
+    if !(1e-7..1e21).contains(&abs) {
+        let sci = format!("{:.14e}", n);
+        return match sci.split_once('e') {
+            Some((mantissa, exp)) => format!("{}e{}", trim_fraction(mantissa), exp),
+            None => sci,
+        };
+    }
+
+    let decimals = (14 - abs.log10().floor() as i32).max(0) as usize;
+    trim_fraction(&format!("{:.*}", decimals, n)).to_string()
+}
+
+// Drop trailing zeros after the decimal point and the point itself if nothing is left
+fn trim_fraction(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Value::Null => write!(f, "null"),
             Value::Bool(b) => write!(f, "{b}"),
-            Value::Num(n) => write!(f, "{}", n.0),
+            Value::Num(n) => write!(f, "{}", format_num(n.0)),
             Value::Str(s) => write!(f, "{}", s.borrow()),
             Value::List(l) => {
                 write!(
@@ -94,6 +130,11 @@ impl Display for Value {
 }
 
 impl Value {
+    /// A Str value holding a copy of `s`
+    pub fn str(s: &str) -> Value {
+        Value::Str(Rc::new(RefCell::new(s.into())))
+    }
+
     pub fn prototype<'a>(&self, prototypes: &'a ValuePrototypes) -> Option<&'a Prototype> {
         match self {
             Value::Num(_) => Some(&prototypes.num),
@@ -202,7 +243,82 @@ impl Value {
         )))
     }
 
+    /// Check that the value is an integer Num index into a sequence of `len` elements
+    pub fn check_index(
+        &self,
+        len: usize,
+        cursor: Cursor,
+        name: Option<String>,
+    ) -> EvalResult<usize> {
+        let name = match name {
+            Some(val) => val,
+            None => "index".to_string(),
+        };
+        let idx = self.check_num(cursor, Some(name.clone()))?;
+        if idx.fract() != 0. {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!("{} must be an integer, found {}", name, idx),
+                cursor,
+            ));
+        }
+        if idx < 0. || idx as usize >= len {
+            return Err(RuntimeEvent::error(
+                ErrKind::Value,
+                format!("{} {} out of bounds (len = {})", name, idx, len),
+                cursor,
+            ));
+        }
+        Ok(idx as usize)
+    }
+
+    /// Compare two values for the ordering operators, only Num-Num, Bool-Bool and Str-Str pairs
+    /// are ordered and other pairs raise a TypeErr. Returns `None` for unordered pairs of Nums
+    /// (NaN).
+    pub fn compare(&self, other: &Value, cursor: Cursor) -> EvalResult<Option<Ordering>> {
+        match (self, other) {
+            (Value::Num(n), Value::Num(on)) => Ok(n.0.partial_cmp(&on.0)),
+            // false orders before true
+            (Value::Bool(b), Value::Bool(ob)) => Ok(Some(b.cmp(ob))),
+            (Value::Str(s), Value::Str(os)) => {
+                Ok(Some(s.borrow().as_str().cmp(os.borrow().as_str())))
+            }
+            _ => Err(RuntimeEvent::error(
+                ErrKind::Type,
+                format!(
+                    "cannot compare values of type {} and {}",
+                    self.get_type(),
+                    other.get_type()
+                ),
+                cursor,
+            )),
+        }
+    }
+
+    /// Same as `to_string`, but a Str is quoted so that "1" and 1 can be told apart
+    pub fn to_quoted_string(&self) -> String {
+        match self {
+            Value::Str(s) => format!("\"{}\"", s.borrow()),
+            other => other.to_string(),
+        }
+    }
+
+    /// Whether both are the very same callable or object, not just ones with the same name
+    pub fn is_same(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Callable(a), Value::Callable(b)) => Rc::ptr_eq(a, b),
+            (Value::Obj(a), Value::Obj(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
     pub fn is_equal(&self, other: &Value) -> bool {
+        self.is_equal_visited(other, &mut Vec::new())
+    }
+
+    /// Structural equality, `visited` holds the pairs of lists/dicts/instances currently being
+    /// compared so self-referential values don't recurse forever
+    fn is_equal_visited(&self, other: &Value, visited: &mut Vec<(usize, usize)>) -> bool {
         match self {
             Value::Null => {
                 if let Value::Null = other {
@@ -228,12 +344,52 @@ impl Value {
                 }
                 return false;
             }
-            Value::List(_) => {
-                // TODO: implement list eq
+            Value::List(l) => {
+                if let Value::List(ol) = other {
+                    if Rc::ptr_eq(l, ol) {
+                        return true;
+                    }
+                    let pair = (Rc::as_ptr(l) as usize, Rc::as_ptr(ol) as usize);
+                    if visited.contains(&pair) {
+                        return true;
+                    }
+                    let (items, other_items) = (l.borrow(), ol.borrow());
+                    if items.len() != other_items.len() {
+                        return false;
+                    }
+                    visited.push(pair);
+                    let eq = items
+                        .iter()
+                        .zip(other_items.iter())
+                        .all(|(a, b)| a.is_equal_visited(b, visited));
+                    visited.pop();
+                    return eq;
+                }
                 return false;
             }
-            Value::Dict(_) => {
-                // TODO: implement dict eq
+            Value::Dict(d) => {
+                if let Value::Dict(od) = other {
+                    if Rc::ptr_eq(d, od) {
+                        return true;
+                    }
+                    let pair = (Rc::as_ptr(d) as usize, Rc::as_ptr(od) as usize);
+                    if visited.contains(&pair) {
+                        return true;
+                    }
+                    let (entries, other_entries) = (d.borrow(), od.borrow());
+                    if entries.len() != other_entries.len() {
+                        return false;
+                    }
+                    visited.push(pair);
+                    // key order doesn't matter, only the value stored under each key
+                    let eq = entries.iter().all(|(key, val)| {
+                        other_entries
+                            .get(key)
+                            .is_some_and(|other_val| val.is_equal_visited(other_val, visited))
+                    });
+                    visited.pop();
+                    return eq;
+                }
                 return false;
             }
             Value::Obj(o) => {
@@ -248,8 +404,31 @@ impl Value {
                 }
                 return false;
             }
-            Value::ObjInstance(_) => {
-                // TODO: implement obj instance eq
+            Value::ObjInstance(i) => {
+                if let Value::ObjInstance(oi) = other {
+                    if Rc::ptr_eq(i, oi) {
+                        return true;
+                    }
+                    let pair = (Rc::as_ptr(i) as usize, Rc::as_ptr(oi) as usize);
+                    if visited.contains(&pair) {
+                        return true;
+                    }
+                    let (inst, other_inst) = (i.borrow(), oi.borrow());
+                    if inst.obj.name != other_inst.obj.name
+                        || inst.fields().len() != other_inst.fields().len()
+                    {
+                        return false;
+                    }
+                    visited.push(pair);
+                    let eq = inst.fields().iter().all(|(name, val)| {
+                        other_inst
+                            .fields()
+                            .get(name)
+                            .is_some_and(|other_val| val.is_equal_visited(other_val, visited))
+                    });
+                    visited.pop();
+                    return eq;
+                }
                 return false;
             }
         }
@@ -265,6 +444,18 @@ impl Value {
         }
     }
 
+    /// The value `v op rhs` stores into the assignment target `v`
+    pub fn apply_assign(&self, op: &AssignOp, rhs: Value, cursor: Cursor) -> EvalResult<Value> {
+        match op {
+            AssignOp::Value => Ok(rhs),
+            AssignOp::Add => self.add_assign(rhs, cursor),
+            AssignOp::Sub => self.sub_assign(rhs, cursor),
+            AssignOp::Mult => self.num_assign(rhs, "*=", |a, b| a * b, cursor),
+            AssignOp::Div => self.num_assign(rhs, "/=", |a, b| a / b, cursor),
+            AssignOp::Mod => self.num_assign(rhs, "%=", |a, b| a % b, cursor),
+        }
+    }
+
     pub fn add_assign(&self, rhs: Value, cursor: Cursor) -> EvalResult<Value> {
         match self {
             // number += number
@@ -274,7 +465,10 @@ impl Value {
                 } else {
                     Err(RuntimeEvent::error(
                         ErrKind::Type,
-                        "cannot add-asssign non-Num to Num".into(),
+                        format!(
+                            "cannot use '+=' with values of type Num and {}",
+                            rhs.get_type()
+                        ),
                         cursor,
                     ))
                 }
@@ -288,16 +482,22 @@ impl Value {
                 Ok(Value::Str(s.clone()))
             }
 
-            // list += elem -> push
+            // list += list -> extend, list += elem -> push
             Value::List(vec) => {
-                vec.borrow_mut().push(rhs);
+                match rhs {
+                    // copied first so `list += list` doesn't borrow the same list twice
+                    Value::List(other) => {
+                        let items = other.borrow().clone();
+                        vec.borrow_mut().extend(items);
+                    }
+                    other => vec.borrow_mut().push(other),
+                }
                 Ok(Value::List(vec.clone()))
             }
 
-            // you might want to support ObjInstance here, etc.
             _ => Err(RuntimeEvent::error(
                 ErrKind::Type,
-                "invalid left-hand side for '+='".into(),
+                format!("cannot use '+=' on a value of type {}", self.get_type()),
                 cursor,
             )),
         }
@@ -312,17 +512,43 @@ impl Value {
                 } else {
                     Err(RuntimeEvent::error(
                         ErrKind::Type,
-                        "cannot sub-assign non-Num from Num".into(),
+                        format!(
+                            "cannot use '-=' with values of type Num and {}",
+                            rhs.get_type()
+                        ),
                         cursor,
                     ))
                 }
             }
 
-            // TODO: list -= ???
-            // TODO: string -= ???
+            // Strs and Lists can be added to but there's no obvious way to subtract from them
+            _ => Err(RuntimeEvent::error_with_note(
+                ErrKind::Type,
+                format!("cannot use '-=' on a value of type {}", self.get_type()),
+                "'-=' is only supported on Nums".into(),
+                cursor,
+            )),
+        }
+    }
+
+    /// v *= rhs, v /= rhs and v %= rhs, only defined for Nums
+    fn num_assign(
+        &self,
+        rhs: Value,
+        op: &str,
+        f: fn(f64, f64) -> f64,
+        cursor: Cursor,
+    ) -> EvalResult<Value> {
+        match (self, &rhs) {
+            (Value::Num(n), Value::Num(m)) => Ok(Value::Num(OrderedFloat(f(n.0, m.0)))),
             _ => Err(RuntimeEvent::error(
                 ErrKind::Type,
-                "invalid left-hand side for '-='".into(),
+                format!(
+                    "cannot use '{}' with values of type {} and {}",
+                    op,
+                    self.get_type(),
+                    rhs.get_type()
+                ),
                 cursor,
             )),
         }
@@ -332,6 +558,11 @@ impl Value {
 pub trait Callable: Debug {
     fn name(&self) -> &str;
     fn arity(&self) -> usize;
+    /// Smallest accepted argument count, callables with optional trailing arguments override this
+    /// and take anywhere from `min_arity()` to `arity()` arguments
+    fn min_arity(&self) -> usize {
+        self.arity()
+    }
     fn call(
         &self,
         evaluator: &mut Evaluator,
@@ -373,3 +604,43 @@ impl Into<Value> for ValueKey {
         }
     }
 }
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(n: f64) -> Value {
+        Value::Num(OrderedFloat(n))
+    }
+
+    #[test]
+    fn num_truthiness() {
+        assert!(!num(0.).is_truthy());
+        assert!(!num(-0.).is_truthy());
+        assert!(num(1.).is_truthy());
+        assert!(num(-1.).is_truthy());
+        assert!(num(0.5).is_truthy());
+    }
+
+    #[test]
+    fn num_display() {
+        assert_eq!(num(1000000.).to_string(), "1000000");
+        assert_eq!(num(0.1 + 0.2).to_string(), "0.3");
+        assert_eq!(num(3.0).to_string(), "3");
+        assert_eq!(num(-2.5).to_string(), "-2.5");
+        assert_eq!(num(-0.).to_string(), "0");
+        assert_eq!(num(1e21).to_string(), "1e21");
+        assert_eq!(num(1.5e-8).to_string(), "1.5e-8");
+        assert_eq!(num(123456789.125).to_string(), "123456789.125");
+        assert_eq!(num(f64::INFINITY).to_string(), "inf");
+    }
+
+    #[test]
+    fn other_truthiness() {
+        assert!(!Value::Null.is_truthy());
+        assert!(!Value::Bool(false).is_truthy());
+        assert!(Value::Bool(true).is_truthy());
+        assert!(Value::Str(Rc::new(RefCell::new(String::new()))).is_truthy());
+    }
+}
@@ -28,6 +28,8 @@ pub struct Lexer {
     start: usize,
     /// Current cursor location
     cursor: Cursor,
+    /// Brace depth of each open `${...}` string interpolation, innermost last
+    interp_depth: Vec<usize>,
     /// Output
     out: LexerOutput,
 }
@@ -39,6 +41,7 @@ impl Lexer {
             curr: 0,
             start: 0,
             cursor: Cursor::new(),
+            interp_depth: vec![],
             out: LexerOutput::default(),
         }
     }
@@ -49,6 +52,7 @@ impl Lexer {
         while !self.is_at_end() {
             // Scan current char and identify token
             self.start = self.curr;
+            let start_cursor = self.cursor;
             let kind = self.scan_char();
 
             // Get lexeme of the identified token
@@ -56,11 +60,15 @@ impl Lexer {
 
             // Build token
             if let Some(kind) = kind {
-                let token = Token::new(kind, lexeme, self.cursor.clone());
+                let token = Token::new(kind, lexeme, start_cursor);
                 tokens.push(token);
             }
         }
 
+        if !self.interp_depth.is_empty() {
+            self.error("unterminated string interpolation".into(), self.cursor);
+        }
+
         if let Some(token) = tokens.last() {
             if token.kind != TokenKind::EOL {
                 tokens.push(Token::new(TokenKind::EOL, "".into(), self.cursor.clone()));
@@ -79,8 +87,9 @@ impl Lexer {
         let token = match c {
             // Types
             '"' => {
-                let s = self.consume_string();
-                Some(TokenKind::Str(s))
+                // skip opening quote
+                self.next();
+                Some(self.consume_string())
             }
             // Assign
             '=' => {
@@ -121,24 +130,56 @@ impl Lexer {
                 if self.consume('*') {
                     self.next();
                     return Some(TokenKind::Pow);
+                } else if self.consume('=') {
+                    self.next();
+                    return Some(TokenKind::MultAssign);
                 }
 
                 self.next();
                 Some(TokenKind::Mult)
             }
             '/' => {
+                if self.consume('/') {
+                    self.next();
+                    return Some(TokenKind::FloorDiv);
+                } else if self.consume('=') {
+                    self.next();
+                    return Some(TokenKind::DivAssign);
+                }
+
                 self.next();
                 Some(TokenKind::Div)
             }
             '%' => {
+                if self.consume('=') {
+                    self.next();
+                    return Some(TokenKind::ModAssign);
+                }
+
                 self.next();
                 Some(TokenKind::Mod)
             }
+            // Bitwise ops
+            '&' => {
+                self.next();
+                Some(TokenKind::BitAnd)
+            }
+            '|' => {
+                self.next();
+                Some(TokenKind::BitOr)
+            }
+            '^' => {
+                self.next();
+                Some(TokenKind::BitXor)
+            }
             // Bool ops
             '<' => {
                 if self.consume('=') {
                     self.next();
                     return Some(TokenKind::LesserEquals);
+                } else if self.consume('<') {
+                    self.next();
+                    return Some(TokenKind::ShiftLeft);
                 }
 
                 self.next();
@@ -148,6 +189,9 @@ impl Lexer {
                 if self.consume('=') {
                     self.next();
                     return Some(TokenKind::GreaterEquals);
+                } else if self.consume('>') {
+                    self.next();
+                    return Some(TokenKind::ShiftRight);
                 }
 
                 self.next();
@@ -193,11 +237,23 @@ impl Lexer {
                 Some(TokenKind::RBracket)
             }
             '{' => {
+                if let Some(depth) = self.interp_depth.last_mut() {
+                    *depth += 1;
+                }
                 self.next();
                 Some(TokenKind::LBrace)
             }
             '}' => {
                 self.next();
+                match self.interp_depth.last_mut() {
+                    // closes an interpolation, continue lexing the rest of the string
+                    Some(0) => {
+                        self.interp_depth.pop();
+                        return Some(self.consume_string());
+                    }
+                    Some(depth) => *depth -= 1,
+                    None => {}
+                }
                 Some(TokenKind::RBrace)
             }
             ',' => {
@@ -233,11 +289,15 @@ impl Lexer {
             }
 
             '#' => {
-                // consume comment chars, stop before newline (so it will emit EOL on next loop)
-                self.next(); // skip '#'
-                while !self.is_at_end() && self.current() != '\n' {
-                    self.next();
+                if self.peek() == '[' {
+                    self.consume_block_comment();
+                    return None;
                 }
+
+                // consume comment chars, stop before newline (so it will emit EOL on next loop),
+                // a comment on the last line has no newline and just ends at EOF
+                self.next(); // skip '#'
+                let _ = self.consume_until('\n');
                 None
             }
             ' ' | '\t' => {
@@ -298,57 +358,101 @@ impl Lexer {
         None
     }
 
+    /// Lex a number literal into its decimal text, `_` separators are allowed between digits
+    /// and `0x`, `0b` and `0o` prefixes give hexadecimal, binary and octal integers
     fn check_num(&mut self) -> Option<String> {
         if !self.current().is_numeric() {
             return None;
         }
+        let start = self.cursor;
 
-        let mut num = String::new();
-        let mut seen_dot = false;
+        let radix = match (self.current(), self.peek()) {
+            ('0', 'x') => Some(16),
+            ('0', 'b') => Some(2),
+            ('0', 'o') => Some(8),
+            _ => None,
+        };
+        if let Some(radix) = radix {
+            self.next(); // move onto the prefix char
+            let prefix = format!("0{}", self.current());
+            // letters are consumed too so a bad digit is an error rather than an identifier
+            let digits = self.consume_digits(|c| c.is_ascii_alphanumeric());
+            return Some(self.radix_num(&prefix, &digits, radix, start));
+        }
 
         // consume the first digit (current)
-        num.push(self.current());
-
-        loop {
-            let nxt = self.peek();
-
-            // more digits?
-            if nxt.is_numeric() {
-                self.next(); // move onto that digit
-                num.push(self.current());
-                continue;
-            }
+        let mut num = self.current().to_string();
+        num += &self.consume_digits(|c| c.is_numeric());
+
+        // optional single '.' with a digit after it
+        let after_dot = self.src.get(self.curr + 2).copied().unwrap_or(' ');
+        if self.peek() == '.' && after_dot.is_numeric() {
+            self.next(); // move onto '.'
+            self.next(); // move onto first frac digit
+            num.push('.');
+            num.push(self.current());
+            num += &self.consume_digits(|c| c.is_numeric());
+        }
 
-            // optional single '.' with a digit after it
-            if !seen_dot && nxt == '.' {
-                // ensure we have a digit after the dot
-                let after_dot = if self.curr + 2 < self.src.len() {
-                    self.src[self.curr + 2]
-                } else {
-                    ' '
-                };
-                if after_dot.is_numeric() {
-                    seen_dot = true;
-                    self.next(); // move onto '.'
-                    num.push('.');
+        if !self.check_separators(&num, &num, start) {
+            return Some("0".into());
+        }
+        Some(num.replace('_', ""))
+    }
 
-                    self.next(); // move onto first frac digit
-                    num.push(self.current());
+    /// Consume the digits (and `_` separators) following the current char, stops on the last
+    /// one WITHOUT advancing past it
+    fn consume_digits(&mut self, is_digit: impl Fn(char) -> bool) -> String {
+        let mut digits = String::new();
+        while is_digit(self.peek()) || self.peek() == '_' {
+            self.next();
+            digits.push(self.current());
+        }
+        digits
+    }
 
-                    // consume remaining fractional digits
-                    while self.peek().is_numeric() {
-                        self.next();
-                        num.push(self.current());
-                    }
-                    continue;
-                }
+    /// Convert the digits of a `0x`/`0b`/`0o` literal to decimal text
+    fn radix_num(&mut self, prefix: &str, digits: &str, radix: u32, start: Cursor) -> String {
+        let literal = format!("{}{}", prefix, digits);
+        if digits.is_empty() {
+            self.error(format!("missing digits after '{}'", prefix), start);
+            return "0".into();
+        }
+        if !self.check_separators(digits, &literal, start) {
+            return "0".into();
+        }
+        if let Some(c) = digits.chars().find(|c| *c != '_' && !c.is_digit(radix)) {
+            self.error(format!("invalid digit '{}' in '{}'", c, literal), start);
+            return "0".into();
+        }
+        match u64::from_str_radix(&digits.replace('_', ""), radix) {
+            Ok(n) => n.to_string(),
+            Err(_) => {
+                self.error(format!("number literal '{}' is too large", literal), start);
+                "0".into()
             }
+        }
+    }
 
-            // next char is not part of the number → stop WITHOUT advancing
-            break;
+    /// `_` separators in `num` have to sit between two digits, reports an error for `literal`
+    /// if they don't
+    fn check_separators(&mut self, num: &str, literal: &str, start: Cursor) -> bool {
+        let misplaced = num.starts_with('_')
+            || num.ends_with('_')
+            || ["__", "_.", "._"].iter().any(|s| num.contains(s));
+        if misplaced {
+            self.error(
+                format!("misplaced '_' separator in number literal '{}'", literal),
+                start,
+            );
         }
+        !misplaced
+    }
 
-        Some(num)
+    fn error(&mut self, msg: String, cursor: Cursor) {
+        self.out.error_count += 1;
+        let err = LexErr { msg, cursor };
+        self.out.errors.get_or_insert(Vec::new()).push(err);
     }
 
     // Iter utils
@@ -362,6 +466,11 @@ impl Lexer {
     }
 
     fn next(&mut self) -> char {
+        // never step past the end of the source
+        if self.is_at_end() {
+            return ' ';
+        }
+
         // Advance cursor
         if self.current() == '\n' {
             self.cursor.next_line();
@@ -419,25 +528,26 @@ impl Lexer {
         false
     }
 
-    fn consume_until(&mut self, c: char) -> String {
+    /// Consume chars up to (not including) `c`, returns None if the end of the source is reached
+    /// before finding `c`
+    fn consume_until(&mut self, c: char) -> Option<String> {
         let mut out = String::new();
 
-        loop {
-            out.push(self.current());
-
-            if self.peek() == c {
-                break;
+        while !self.is_at_end() {
+            if self.current() == c {
+                return Some(out);
             }
+            out.push(self.current());
             self.next();
         }
 
-        out
+        None
     }
 
-    fn consume_string(&mut self) -> String {
+    /// Lex string contents up to the closing quote or the next `${`, returns a Str token for the
+    /// former and an InterpStr token for the latter
+    fn consume_string(&mut self) -> TokenKind {
         let mut out = String::new();
-        // skip opening quote
-        self.next();
         let mut terminated = false;
 
         while !self.is_at_end() {
@@ -449,7 +559,21 @@ impl Lexer {
                 break;
             }
 
+            if ch == '$' && self.peek() == '{' {
+                // skip '${', the expression is lexed as regular tokens until the matching '}'
+                self.next();
+                self.next();
+                self.interp_depth.push(0);
+                return TokenKind::InterpStr(out);
+            }
+
             if ch == '\\' {
+                if self.curr + 1 >= self.src.len() {
+                    // a backslash at the end of the source escapes nothing, the string is just
+                    // unterminated
+                    self.next();
+                    break;
+                }
                 let esc = self.peek();
                 let mapped = match esc {
                     '\\' => Some('\\'),
@@ -457,19 +581,23 @@ impl Lexer {
                     'n' => Some('\n'),
                     't' => Some('\t'),
                     'r' => Some('\r'),
+                    '$' => Some('$'),
                     _ => None,
                 };
-                // advance over the escape char
+                if mapped.is_none() {
+                    // unknown escape, report it at the backslash
+                    self.error(format!("unknown escape sequence '\\{}'", esc), self.cursor);
+                }
+
+                // advance over the backslash and the escape char
                 self.next();
-                if let Some(m) = mapped {
+                if !self.is_at_end() {
                     self.next();
+                }
+                if let Some(m) = mapped {
                     out.push(m);
-                    continue;
-                } else {
-                    // unknown escape, keep the backslash literal
-                    out.push('\\');
-                    continue;
                 }
+                continue;
             }
 
             out.push(ch);
@@ -477,31 +605,45 @@ impl Lexer {
         }
 
         if !terminated {
-            self.out.error_count += 1;
-            let err = LexErr {
-                msg: "unterminated string literal".into(),
-                cursor: self.cursor,
-            };
-            self.out.errors.get_or_insert(Vec::new()).push(err.clone());
+            self.error("unterminated string literal".into(), self.cursor);
+        }
+
+        TokenKind::Str(out)
+    }
+
+    /// Skip a `#[ ... ]#` block comment, newlines inside it don't emit EOL tokens
+    fn consume_block_comment(&mut self) {
+        let start_cursor = self.cursor;
+        // skip '#['
+        self.next();
+        self.next();
+
+        while !self.is_at_end() {
+            if self.current() == ']' && self.peek() == '#' {
+                self.next();
+                self.next();
+                return;
+            }
+            self.next();
         }
 
-        out
+        self.error("unterminated block comment".into(), start_cursor);
     }
 
     fn get_lexeme(&self) -> String {
-        if self.is_at_end() {
+        let end = self.curr.min(self.src.len());
+        if self.start >= end {
             return "".into();
         }
 
-        let len = self.curr - self.start;
-        self.src[self.start..self.start + len]
+        self.src[self.start..end]
             .iter()
             .map(|&c| c as char)
             .collect()
     }
 
     fn is_at_end(&self) -> bool {
-        self.curr == self.src.len()
+        self.curr >= self.src.len()
     }
 }
 
@@ -520,11 +662,45 @@ mod tests {
             .collect()
     }
 
+    fn errors(src: &str) -> Vec<String> {
+        let mut lx = Lexer::new(src.to_string());
+        lx.tokenize()
+            .errors
+            .unwrap_or_default()
+            .iter()
+            .map(|err| err.msg.clone())
+            .collect()
+    }
+
     #[test]
     fn empty_input() {
         assert_eq!(tokens(""), vec![TokenKind::EOF]);
     }
 
+    #[test]
+    fn compound_assign() {
+        assert_eq!(
+            tokens("a *= 2 ** 2 /= 3 // 4 %= 5 % 6"),
+            vec![
+                TokenKind::Identifier("a".into()),
+                TokenKind::MultAssign,
+                TokenKind::Num("2".into()),
+                TokenKind::Pow,
+                TokenKind::Num("2".into()),
+                TokenKind::DivAssign,
+                TokenKind::Num("3".into()),
+                TokenKind::FloorDiv,
+                TokenKind::Num("4".into()),
+                TokenKind::ModAssign,
+                TokenKind::Num("5".into()),
+                TokenKind::Mod,
+                TokenKind::Num("6".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+    }
+
     #[test]
     fn simple_assign() {
         assert_eq!(
@@ -636,6 +812,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tabs_and_crlf() {
+        assert_eq!(tokens("a\t=\t1\r\n"), tokens("a = 1\n"));
+        assert_eq!(
+            tokens("a\r\n\r\nb"),
+            vec![
+                TokenKind::Identifier("a".into()),
+                TokenKind::EOL,
+                TokenKind::EOL,
+                TokenKind::Identifier("b".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        // comments stop before the line ending without swallowing the '\r'
+        assert_eq!(tokens("# note\r\nx\r\n"), tokens("# note\nx\n"));
+    }
+
+    #[test]
+    fn number_literals() {
+        for (src, num) in [
+            ("1_000", "1000"),
+            ("1_000.000_5", "1000.0005"),
+            ("0xFF", "255"),
+            ("0b1010", "10"),
+            ("0o17", "15"),
+            ("0b1111_0000", "240"),
+        ] {
+            assert_eq!(
+                tokens(src),
+                vec![TokenKind::Num(num.into()), TokenKind::EOL, TokenKind::EOF],
+                "{}",
+                src
+            );
+        }
+        // a range end isn't mistaken for a fraction
+        assert_eq!(
+            tokens("1..2")[..3],
+            [
+                TokenKind::Num("1".into()),
+                TokenKind::Range,
+                TokenKind::Num("2".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_number_literals() {
+        assert_eq!(
+            errors("1__0"),
+            vec!["misplaced '_' separator in number literal '1__0'"]
+        );
+        assert_eq!(
+            errors("1_"),
+            vec!["misplaced '_' separator in number literal '1_'"]
+        );
+        assert_eq!(
+            errors("1_.5"),
+            vec!["misplaced '_' separator in number literal '1_.5'"]
+        );
+        assert_eq!(errors("0x"), vec!["missing digits after '0x'"]);
+        assert_eq!(
+            errors("0x_1"),
+            vec!["misplaced '_' separator in number literal '0x_1'"]
+        );
+        assert_eq!(errors("0b102"), vec!["invalid digit '2' in '0b102'"]);
+        assert_eq!(
+            errors("0xFFFFFFFFFFFFFFFFF"),
+            vec!["number literal '0xFFFFFFFFFFFFFFFFF' is too large"]
+        );
+    }
+
     #[test]
     fn string_at_eof() {
         assert_eq!(
@@ -665,6 +913,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn block_comment() {
+        assert_eq!(
+            tokens("a #[ one\ntwo\nthree ]# b\n#[]#c\n"),
+            vec![
+                TokenKind::Identifier("a".into()),
+                TokenKind::Identifier("b".into()),
+                TokenKind::EOL,
+                TokenKind::Identifier("c".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_block_comment() {
+        assert_eq!(
+            errors("x\n#[ never closed\n]\n"),
+            vec!["unterminated block comment"]
+        );
+    }
+
     #[test]
     fn keywords_vs_identifiers() {
         assert_eq!(
@@ -680,4 +951,180 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(
+            tokens("\"a\\tb\""),
+            vec![
+                TokenKind::Str("a\tb".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        assert_eq!(
+            tokens("\"he said \\\"hi\\\"\""),
+            vec![
+                TokenKind::Str("he said \"hi\"".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        assert_eq!(
+            tokens("\"a\\\\b\\nc\\r\""),
+            vec![
+                TokenKind::Str("a\\b\nc\r".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_string_escape() {
+        assert_eq!(tokens("\"a\\qb\""), vec![]);
+        assert_eq!(errors("\"a\\qb\""), vec!["unknown escape sequence '\\q'"]);
+    }
+
+    #[test]
+    fn string_interpolation() {
+        assert_eq!(
+            tokens("\"val=${x}!\""),
+            vec![
+                TokenKind::InterpStr("val=".into()),
+                TokenKind::Identifier("x".into()),
+                TokenKind::Str("!".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        // braces inside the expression don't end the interpolation
+        assert_eq!(
+            tokens("\"${{1: 2}[1]}\""),
+            vec![
+                TokenKind::InterpStr("".into()),
+                TokenKind::LBrace,
+                TokenKind::Num("1".into()),
+                TokenKind::Colon,
+                TokenKind::Num("2".into()),
+                TokenKind::RBrace,
+                TokenKind::LBracket,
+                TokenKind::Num("1".into()),
+                TokenKind::RBracket,
+                TokenKind::Str("".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        assert_eq!(
+            tokens("\"\\$5 ${\"\"}\""),
+            vec![
+                TokenKind::InterpStr("$5 ".into()),
+                TokenKind::Str("".into()),
+                TokenKind::Str("".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        assert_eq!(errors("\"a${x"), vec!["unterminated string interpolation"]);
+    }
+
+    #[test]
+    fn unterminated_string() {
+        // a backslash right before the end escapes nothing
+        for src in ["\"abc", "\"", "var s = \"abc\n", "\"abc\\"] {
+            assert_eq!(tokens(src), vec![], "{}", src);
+            assert_eq!(errors(src), vec!["unterminated string literal"], "{}", src);
+        }
+    }
+
+    #[test]
+    fn comment_at_eof() {
+        assert_eq!(
+            tokens("x # no newline"),
+            vec![
+                TokenKind::Identifier("x".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        assert_eq!(tokens("#"), vec![TokenKind::EOF]);
+        assert!(errors("# comment").is_empty());
+    }
+
+    #[test]
+    fn last_token_lexeme() {
+        let tokens = Lexer::new("var abc".to_string())
+            .tokenize()
+            .tokens
+            .unwrap_or_default();
+        assert_eq!(tokens[1].lexeme, "abc");
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        assert_eq!(
+            tokens("a & b | c ^ d << 1 >> 2 <= 3"),
+            vec![
+                TokenKind::Identifier("a".into()),
+                TokenKind::BitAnd,
+                TokenKind::Identifier("b".into()),
+                TokenKind::BitOr,
+                TokenKind::Identifier("c".into()),
+                TokenKind::BitXor,
+                TokenKind::Identifier("d".into()),
+                TokenKind::ShiftLeft,
+                TokenKind::Num("1".into()),
+                TokenKind::ShiftRight,
+                TokenKind::Num("2".into()),
+                TokenKind::LesserEquals,
+                TokenKind::Num("3".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn floor_div() {
+        assert_eq!(
+            tokens("7 // 2 / 1"),
+            vec![
+                TokenKind::Num("7".into()),
+                TokenKind::FloorDiv,
+                TokenKind::Num("2".into()),
+                TokenKind::Div,
+                TokenKind::Num("1".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+        assert_eq!(
+            tokens("a / /b"),
+            vec![
+                TokenKind::Identifier("a".into()),
+                TokenKind::Div,
+                TokenKind::Div,
+                TokenKind::Identifier("b".into()),
+                TokenKind::EOL,
+                TokenKind::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn token_positions() {
+        let mut lx = Lexer::new("a\nb".to_string());
+        let tokens = lx.tokenize().tokens.unwrap_or_default();
+
+        // `b` starts the second line (reported as line 2)
+        assert_eq!(tokens[2].kind, TokenKind::Identifier("b".into()));
+        assert_eq!(tokens[2].cursor, Cursor { line: 1, col: 0 });
+        assert_eq!(tokens[0].cursor, Cursor { line: 0, col: 0 });
+
+        let mut lx = Lexer::new("var foo = 10".to_string());
+        let tokens = lx.tokenize().tokens.unwrap_or_default();
+        assert_eq!(tokens[1].cursor, Cursor { line: 0, col: 4 });
+        assert_eq!(tokens[3].cursor, Cursor { line: 0, col: 10 });
+    }
 }
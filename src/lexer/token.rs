@@ -1,19 +1,26 @@
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use strum::EnumDiscriminants;
 
 use crate::lexer::cursor::Cursor;
 
-#[derive(Debug, PartialEq, Clone, EnumDiscriminants)]
+#[derive(Debug, PartialEq, Clone, EnumDiscriminants, Serialize, Deserialize)]
 pub enum TokenKind {
     // Literals
     Num(String),
     Bool(bool),
     Str(String),
+    /// String part that is followed by an interpolated `${expr}`, the rest of the string comes
+    /// after the expression tokens as another InterpStr or a closing Str
+    InterpStr(String),
     Null,
     // Assign
     Assign,
     AddAssign,
     SubAssign,
+    MultAssign,
+    DivAssign,
+    ModAssign,
     Incr,
     Decr,
     // Arithmetic
@@ -21,8 +28,15 @@ pub enum TokenKind {
     Sub,
     Mult,
     Div,
+    FloorDiv,
     Mod,
     Pow,
+    // Bitwise
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     // Boolean
     Not,
     Equals,
@@ -59,11 +73,15 @@ impl ToString for TokenKindDiscriminants {
             TokenKindDiscriminants::Num => "Num",
             TokenKindDiscriminants::Bool => "Bool",
             TokenKindDiscriminants::Str => "Str",
+            TokenKindDiscriminants::InterpStr => "InterpStr",
 
             // Assign
             TokenKindDiscriminants::Assign => "Assign",
             TokenKindDiscriminants::AddAssign => "AddAssign",
             TokenKindDiscriminants::SubAssign => "SubAssign",
+            TokenKindDiscriminants::MultAssign => "MultAssign",
+            TokenKindDiscriminants::DivAssign => "DivAssign",
+            TokenKindDiscriminants::ModAssign => "ModAssign",
             TokenKindDiscriminants::Incr => "Incr",
             TokenKindDiscriminants::Decr => "Decr",
 
@@ -72,9 +90,17 @@ impl ToString for TokenKindDiscriminants {
             TokenKindDiscriminants::Sub => "Sub",
             TokenKindDiscriminants::Mult => "Mult",
             TokenKindDiscriminants::Div => "Div",
+            TokenKindDiscriminants::FloorDiv => "FloorDiv",
             TokenKindDiscriminants::Mod => "Mod",
             TokenKindDiscriminants::Pow => "Pow",
 
+            // Bitwise
+            TokenKindDiscriminants::BitAnd => "BitAnd",
+            TokenKindDiscriminants::BitOr => "BitOr",
+            TokenKindDiscriminants::BitXor => "BitXor",
+            TokenKindDiscriminants::ShiftLeft => "ShiftLeft",
+            TokenKindDiscriminants::ShiftRight => "ShiftRight",
+
             // Boolean
             TokenKindDiscriminants::Not => "Not",
             TokenKindDiscriminants::Equals => "Equals",
@@ -110,7 +136,7 @@ impl ToString for TokenKindDiscriminants {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum KeywordKind {
     Do,
     End,
@@ -127,6 +153,7 @@ pub enum KeywordKind {
     Var,
     And,
     Or,
+    Not,
     Step,
     In,
     Fn,
@@ -155,6 +182,7 @@ impl ToString for KeywordKind {
             KeywordKind::Var => "var",
             KeywordKind::And => "and",
             KeywordKind::Or => "or",
+            KeywordKind::Not => "not",
             KeywordKind::Step => "step",
             KeywordKind::In => "in",
             KeywordKind::Fn => "fn",
@@ -188,6 +216,7 @@ impl FromStr for KeywordKind {
             "var" => Ok(KeywordKind::Var),
             "and" => Ok(KeywordKind::And),
             "or" => Ok(KeywordKind::Or),
+            "not" => Ok(KeywordKind::Not),
             "step" => Ok(KeywordKind::Step),
             "in" => Ok(KeywordKind::In),
             "fn" => Ok(KeywordKind::Fn),
@@ -201,7 +230,7 @@ impl FromStr for KeywordKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     /// Kind of the token
     pub kind: TokenKind,
@@ -1,5 +1,6 @@
-use clap::Parser as ClapParser;
-use std::path::PathBuf;
+use clap::{Parser as ClapParser, ValueEnum};
+use serde::Serialize;
+use std::{fmt::Debug, path::PathBuf};
 
 use crate::{
     evaluator::{Evaluator, resolver::Resolver},
@@ -12,8 +13,10 @@ use crate::{
 pub mod evaluator;
 pub mod lexer;
 pub mod parser;
+pub mod repl;
 pub mod reporter;
 pub mod src;
+pub mod watch;
 
 #[derive(ClapParser, Debug)]
 #[command(
@@ -23,8 +26,16 @@ pub mod src;
     author = "qewer33"
 )]
 struct Args {
-    /// Program file to run
-    file: PathBuf,
+    /// Program file to run, starts the REPL if omitted
+    file: Option<PathBuf>,
+
+    /// Run the given code instead of a program file
+    #[arg(short, long, value_name = "CODE")]
+    eval: Option<String>,
+
+    /// Arguments passed to the program, available through Sys.args()
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
 
     /// Dump token stream and exit
     #[arg(long, conflicts_with_all = ["dump_ast", "verbose"])]
@@ -37,14 +48,77 @@ struct Args {
     /// Dump tokens and AST, then execute
     #[arg(long)]
     verbose: bool,
+
+    /// Print the program's global variables to stderr after each top-level statement
+    #[arg(long)]
+    trace: bool,
+
+    /// Run the program file again every time it's saved
+    #[arg(long, requires = "file", conflicts_with_all = ["eval", "dump_tokens", "dump_ast", "verbose"])]
+    watch: bool,
+
+    /// Output format of --dump-tokens, --dump-ast and --verbose
+    #[arg(long, value_enum, default_value_t = DumpFormat::Debug)]
+    dump_format: DumpFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum DumpFormat {
+    /// Rust debug output on stderr
+    Debug,
+    /// JSON on stdout
+    Json,
+}
+
+/// Dump `val` in the given format, `title` is only printed for the debug format so JSON
+/// output stays machine readable
+fn dump<T: Serialize + Debug>(title: &str, val: &T, format: DumpFormat) {
+    match format {
+        DumpFormat::Debug => {
+            println!("== {} ==", title);
+            dbg!(val);
+        }
+        DumpFormat::Json => println!("{}", to_json(val)),
+    }
+}
+
+fn to_json<T: Serialize>(val: &T) -> String {
+    serde_json::to_string_pretty(val).expect("tokens and AST should serialize to JSON")
 }
 
 fn main() {
     let args = Args::parse();
 
     // 1) Read source
-    let mut src = Src::new(args.file);
+    let (src, program_args) = match (args.eval.clone(), args.file.clone()) {
+        // with --eval there is no program file, so a positional argument belongs to the program
+        (Some(code), file) => {
+            let mut program_args: Vec<String> = file
+                .map(|f| f.to_string_lossy().to_string())
+                .into_iter()
+                .collect();
+            program_args.extend(args.args.clone());
+            (Src::from_text(PathBuf::from("<eval>"), code), program_args)
+        }
+        (None, Some(file)) if args.watch => {
+            watch::run(file, &args);
+            return;
+        }
+        (None, Some(file)) => (Src::new(file), args.args.clone()),
+        (None, None) => {
+            repl::run();
+            return;
+        }
+    };
+
+    if !run_src(src, program_args, &args) {
+        std::process::exit(1);
+    }
+}
 
+/// Lex, parse, resolve and evaluate `src`, dumping tokens and the AST along the way if `args`
+/// asks for it. Errors and warning summaries are reported, returns false if any stage failed
+fn run_src(mut src: Src, program_args: Vec<String>, args: &Args) -> bool {
     // 2) Lex
     let mut lexer = Lexer::new(src.text.clone());
     let lex_out = lexer.tokenize();
@@ -56,15 +130,14 @@ fn main() {
                     Reporter::lex_err_at(err, &src);
                 }
             }
-            std::process::exit(1);
+            return false;
         }
     };
 
     if args.dump_tokens || args.verbose {
-        println!("== TOKENS ==");
-        dbg!(&src.tokens);
+        dump("TOKENS", &src.tokens, args.dump_format);
         if args.dump_tokens {
-            return;
+            return true;
         }
     }
 
@@ -75,7 +148,7 @@ fn main() {
         Some(s) => {
             if parser_out.warning_count > 0 {
                 Reporter::warning(
-                    format!("parser exited with {} warnings", parser_out.error_count).as_str(),
+                    format!("parser exited with {} warnings", parser_out.warning_count).as_str(),
                 );
                 println!();
             }
@@ -83,19 +156,18 @@ fn main() {
             Some(s)
         }
         None => {
-            // Exit on parse error
+            // Stop on parse error
             Reporter::error(
                 format!("parser exited with {} errors", parser_out.error_count).as_str(),
             );
-            std::process::exit(1);
+            return false;
         }
     };
 
     if args.dump_ast || args.verbose {
-        println!("== AST ==");
-        dbg!(&src.ast);
+        dump("AST", &src.ast, args.dump_format);
         if args.dump_ast {
-            return;
+            return true;
         }
     }
 
@@ -118,16 +190,70 @@ fn main() {
             Some(s)
         }
         None => {
-            // Exit on parse error
+            // Stop on resolve error
             Reporter::error(
                 format!("resolver exited with {} errors", resolver_out.error_count).as_str(),
             );
-            std::process::exit(1);
+            return false;
         }
     };
 
-    let mut evaluator = Evaluator::new(&src);
-    if evaluator.eval().is_err() {
-        std::process::exit(1);
+    let mut evaluator = Evaluator::with_args(&src, program_args);
+    evaluator.trace = args.trace;
+    evaluator.eval().is_ok()
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::token::Token;
+
+    #[test]
+    fn trailing_args() {
+        let args = Args::try_parse_from(["queitite", "prog.qt", "foo", "bar"]).unwrap();
+        assert_eq!(args.file, Some(PathBuf::from("prog.qt")));
+        assert_eq!(args.args, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn eval_flag() {
+        let args = Args::try_parse_from(["queitite", "-e", "println(1)", "foo"]).unwrap();
+        assert_eq!(args.eval.as_deref(), Some("println(1)"));
+        assert_eq!(args.file, Some(PathBuf::from("foo")));
+    }
+
+    #[test]
+    fn watch_needs_file() {
+        let args = Args::try_parse_from(["queitite", "--watch", "prog.qte"]).unwrap();
+        assert!(args.watch);
+        assert!(Args::try_parse_from(["queitite", "--watch"]).is_err());
+        assert!(Args::try_parse_from(["queitite", "--watch", "-e", "println(1)"]).is_err());
+    }
+
+    #[test]
+    fn dump_tokens_json() {
+        let args = Args::try_parse_from([
+            "queitite",
+            "--dump-tokens",
+            "--dump-format",
+            "json",
+            "prog.qte",
+        ])
+        .unwrap();
+        assert!(args.dump_tokens);
+        assert_eq!(args.dump_format, DumpFormat::Json);
+
+        let tokens = Lexer::new("var x = [1, \"a\"]\n".into())
+            .tokenize()
+            .tokens
+            .unwrap();
+        let parsed: Vec<Token> = serde_json::from_str(&to_json(&tokens)).unwrap();
+        assert_eq!(
+            parsed.iter().map(|t| &t.kind).collect::<Vec<_>>(),
+            tokens.iter().map(|t| &t.kind).collect::<Vec<_>>()
+        );
+        assert_eq!(parsed[1].lexeme, "x");
+        assert_eq!(parsed[1].cursor, tokens[1].cursor);
     }
 }
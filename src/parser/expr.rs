@@ -1,15 +1,21 @@
 use ordered_float::OrderedFloat;
+use serde::Serialize;
 use std::cell::RefCell;
 
-use crate::lexer::{
-    cursor::Cursor,
-    token::{KeywordKind, TokenKind},
+use crate::{
+    lexer::{
+        cursor::Cursor,
+        token::{KeywordKind, TokenKind},
+    },
+    parser::stmt::Stmt,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum ExprKind {
     Literal(LiteralType),
     List(Vec<Expr>),
+    /// Interpolated string, the parts are stringified and concatenated
+    Interp(Vec<Expr>),
     Dict(Vec<(Expr, Expr)>),
     Range {
         start: Box<Expr>,
@@ -70,9 +76,14 @@ pub enum ExprKind {
         val: Box<Expr>,
     },
     ESelf,
+    Lambda {
+        params: Vec<String>,
+        defaults: Vec<Expr>,
+        body: Box<Stmt>,
+    },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Expr {
     /// Kind of the expression
     pub kind: ExprKind,
@@ -111,7 +122,7 @@ pub enum OpFromTokenError {
     BadNumber(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum LiteralType {
     Null,
     Num(OrderedFloat<f64>),
@@ -119,11 +130,14 @@ pub enum LiteralType {
     Bool(bool),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum AssignOp {
     Value,
     Add,
     Sub,
+    Mult,
+    Div,
+    Mod,
 }
 
 impl TryFrom<&TokenKind> for AssignOp {
@@ -134,6 +148,9 @@ impl TryFrom<&TokenKind> for AssignOp {
             TokenKind::Assign => Ok(AssignOp::Value),
             TokenKind::AddAssign => Ok(AssignOp::Add),
             TokenKind::SubAssign => Ok(AssignOp::Sub),
+            TokenKind::MultAssign => Ok(AssignOp::Mult),
+            TokenKind::DivAssign => Ok(AssignOp::Div),
+            TokenKind::ModAssign => Ok(AssignOp::Mod),
             TokenKind::Incr => Ok(AssignOp::Add),
             TokenKind::Decr => Ok(AssignOp::Sub),
             _ => Err(OpFromTokenError::NotAssign(
@@ -143,7 +160,7 @@ impl TryFrom<&TokenKind> for AssignOp {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum UnaryOp {
     Negate,
     Not,
@@ -156,12 +173,13 @@ impl TryFrom<&TokenKind> for UnaryOp {
         match t {
             TokenKind::Sub => Ok(UnaryOp::Negate), // e.g., prefix minus
             TokenKind::Not => Ok(UnaryOp::Not),
+            TokenKind::Keyword(KeywordKind::Not) => Ok(UnaryOp::Not),
             _ => Err(OpFromTokenError::NotUnary("expected unary operator token")),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum LogicalOp {
     And,
     Or,
@@ -191,15 +209,22 @@ impl TryFrom<&TokenKind> for LogicalOp {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum BinaryOp {
     // Arithmetic
     Add,
     Sub,
     Mult,
     Div,
+    FloorDiv,
     Mod,
     Pow,
+    // Bitwise
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     // Boolean
     Equals,
     NotEquals,
@@ -220,8 +245,15 @@ impl TryFrom<&TokenKind> for BinaryOp {
             TokenKind::Sub => BinaryOp::Sub,
             TokenKind::Mult => BinaryOp::Mult,
             TokenKind::Div => BinaryOp::Div,
+            TokenKind::FloorDiv => BinaryOp::FloorDiv,
             TokenKind::Mod => BinaryOp::Mod,
             TokenKind::Pow => BinaryOp::Pow,
+            // Bitwise
+            TokenKind::BitAnd => BinaryOp::BitAnd,
+            TokenKind::BitOr => BinaryOp::BitOr,
+            TokenKind::BitXor => BinaryOp::BitXor,
+            TokenKind::ShiftLeft => BinaryOp::ShiftLeft,
+            TokenKind::ShiftRight => BinaryOp::ShiftRight,
             // Equality / comparison
             TokenKind::Equals => BinaryOp::Equals,
             TokenKind::NotEquals => BinaryOp::NotEquals,
@@ -28,6 +28,10 @@ pub struct ParserOutput {
 
 impl ParserOutput {
     fn add_stmt(&mut self, stmt: Stmt) {
+        // statements parsed after an error are only checked for more errors
+        if self.errors.is_some() {
+            return;
+        }
         if let None = self.ast {
             self.ast = Some(vec![]);
         }
@@ -73,6 +77,7 @@ impl<'a> Parser<'a> {
         self.skip_eols();
 
         while !self.is_at_end() {
+            let start = self.curr;
             let stmt = self.declr();
 
             match stmt {
@@ -83,7 +88,7 @@ impl<'a> Parser<'a> {
                 Err(err) => {
                     self.out.add_err(err.clone());
                     Reporter::parse_err_at(&err, self.src);
-                    self.synchronize();
+                    self.synchronize(start);
                 }
             }
         }
@@ -99,7 +104,11 @@ impl<'a> Parser<'a> {
         if self.match_keyword(KeywordKind::Var) {
             return self.var_declr(true);
         }
-        if self.match_keyword(KeywordKind::Fn) {
+        // `fn` followed by a name is a declaration, otherwise it's a lambda expression
+        if self.check_keyword(KeywordKind::Fn)
+            && TokenKindDiscriminants::from(&self.peek().kind) == TokenKindDiscriminants::Identifier
+        {
+            self.next();
             return self.fn_declr();
         }
         if self.match_keyword(KeywordKind::Obj) {
@@ -153,10 +162,30 @@ impl<'a> Parser<'a> {
             TokenKindDiscriminants::LParen,
             "expected '(' after function name",
         )?;
+        let (params, defaults, bound) = self.fn_params()?;
 
+        self.consume_keyword(KeywordKind::Do, "expected 'do' before function body")?;
+        let body = self.block_stmt()?;
+        Ok(Stmt::new(
+            StmtKind::Fn {
+                name,
+                params,
+                defaults,
+                body: Box::new(body),
+                bound,
+            },
+            name_token.cursor,
+        ))
+    }
+
+    /// Parses a parameter list up to and including the closing ')', returns the parameter names,
+    /// the default values of the trailing parameters that have one and whether `self` was one of
+    /// them
+    fn fn_params(&mut self) -> ParseResult<(Vec<String>, Vec<Expr>, bool)> {
         let mut bound = false;
 
         let mut params: Vec<String> = vec![];
+        let mut defaults: Vec<Expr> = vec![];
         if !self.check(TokenKindDiscriminants::RParen) {
             loop {
                 if params.len() >= 255 {
@@ -178,6 +207,17 @@ impl<'a> Parser<'a> {
                     )?;
 
                     if let TokenKind::Identifier(name) = ident.kind {
+                        if self.match_tokens(vec![TokenKindDiscriminants::Assign]) {
+                            defaults.push(self.expr()?);
+                        } else if !defaults.is_empty() {
+                            return Err(ParseErr::new(
+                                format!(
+                                    "parameter '{}' needs a default value since it comes after a parameter with one",
+                                    name
+                                ),
+                                ident.cursor,
+                            ));
+                        }
                         params.push(name);
                     }
                 }
@@ -192,18 +232,7 @@ impl<'a> Parser<'a> {
             TokenKindDiscriminants::RParen,
             "expected ')' after function parameters",
         )?;
-
-        self.consume_keyword(KeywordKind::Do, "expected 'do' before function body")?;
-        let body = self.block_stmt()?;
-        Ok(Stmt::new(
-            StmtKind::Fn {
-                name,
-                params,
-                body: Box::new(body),
-                bound,
-            },
-            name_token.cursor,
-        ))
+        Ok((params, defaults, bound))
     }
 
     fn obj_declr(&mut self) -> ParseResult<Stmt> {
@@ -529,6 +558,9 @@ impl<'a> Parser<'a> {
             TokenKindDiscriminants::Assign,
             TokenKindDiscriminants::AddAssign,
             TokenKindDiscriminants::SubAssign,
+            TokenKindDiscriminants::MultAssign,
+            TokenKindDiscriminants::DivAssign,
+            TokenKindDiscriminants::ModAssign,
             TokenKindDiscriminants::Incr,
             TokenKindDiscriminants::Decr,
         ]) {
@@ -660,13 +692,84 @@ impl<'a> Parser<'a> {
     }
 
     fn comparison(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.term()?;
+        let mut expr = self.bit_or()?;
 
         while self.match_tokens(vec![
             TokenKindDiscriminants::Greater,
             TokenKindDiscriminants::GreaterEquals,
             TokenKindDiscriminants::Lesser,
             TokenKindDiscriminants::LesserEquals,
+        ]) {
+            let op = BinaryOp::try_from(&self.previous().kind).unwrap();
+            let right = self.bit_or()?;
+            expr.kind = ExprKind::Binary {
+                left: Box::new(expr.clone()),
+                op,
+                right: Box::new(right),
+            };
+            expr.cursor = self.previous().cursor;
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_or(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.bit_xor()?;
+
+        while self.match_tokens(vec![TokenKindDiscriminants::BitOr]) {
+            let op = BinaryOp::try_from(&self.previous().kind).unwrap();
+            let right = self.bit_xor()?;
+            expr.kind = ExprKind::Binary {
+                left: Box::new(expr.clone()),
+                op,
+                right: Box::new(right),
+            };
+            expr.cursor = self.previous().cursor;
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_xor(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.bit_and()?;
+
+        while self.match_tokens(vec![TokenKindDiscriminants::BitXor]) {
+            let op = BinaryOp::try_from(&self.previous().kind).unwrap();
+            let right = self.bit_and()?;
+            expr.kind = ExprKind::Binary {
+                left: Box::new(expr.clone()),
+                op,
+                right: Box::new(right),
+            };
+            expr.cursor = self.previous().cursor;
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_and(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.shift()?;
+
+        while self.match_tokens(vec![TokenKindDiscriminants::BitAnd]) {
+            let op = BinaryOp::try_from(&self.previous().kind).unwrap();
+            let right = self.shift()?;
+            expr.kind = ExprKind::Binary {
+                left: Box::new(expr.clone()),
+                op,
+                right: Box::new(right),
+            };
+            expr.cursor = self.previous().cursor;
+        }
+
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.term()?;
+
+        while self.match_tokens(vec![
+            TokenKindDiscriminants::ShiftLeft,
+            TokenKindDiscriminants::ShiftRight,
         ]) {
             let op = BinaryOp::try_from(&self.previous().kind).unwrap();
             let right = self.term()?;
@@ -706,9 +809,9 @@ impl<'a> Parser<'a> {
 
         while self.match_tokens(vec![
             TokenKindDiscriminants::Div,
+            TokenKindDiscriminants::FloorDiv,
             TokenKindDiscriminants::Mult,
             TokenKindDiscriminants::Mod,
-            TokenKindDiscriminants::Pow,
             TokenKindDiscriminants::Nullish,
         ]) {
             let op = BinaryOp::try_from(&self.previous().kind).unwrap();
@@ -725,22 +828,43 @@ impl<'a> Parser<'a> {
     }
 
     fn unary(&mut self) -> ParseResult<Expr> {
-        while self.match_tokens(vec![
+        if self.match_tokens(vec![
             TokenKindDiscriminants::Not,
             TokenKindDiscriminants::Sub,
-        ]) {
-            let op = UnaryOp::try_from(&self.previous().kind).unwrap();
+        ]) || self.match_keyword(KeywordKind::Not)
+        {
+            let op_token = self.previous();
+            let op = UnaryOp::try_from(&op_token.kind).unwrap();
             let right = self.unary()?;
             return Ok(Expr::new(
                 ExprKind::Unary {
                     op,
                     right: Box::new(right),
                 },
-                self.previous().cursor,
+                op_token.cursor,
             ));
         }
 
-        Ok(self.call()?)
+        self.power()
+    }
+
+    fn power(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.call()?;
+
+        // right associative, the exponent is parsed as a unary so `2 ** -1` and
+        // `2 ** 3 ** 2` both work
+        if self.match_tokens(vec![TokenKindDiscriminants::Pow]) {
+            let op = BinaryOp::try_from(&self.previous().kind).unwrap();
+            let right = self.unary()?;
+            expr.kind = ExprKind::Binary {
+                left: Box::new(expr.clone()),
+                op,
+                right: Box::new(right),
+            };
+            expr.cursor = self.previous().cursor;
+        }
+
+        Ok(expr)
     }
 
     fn call(&mut self) -> ParseResult<Expr> {
@@ -771,7 +895,7 @@ impl<'a> Parser<'a> {
                             obj: Box::new(expr),
                             name,
                         },
-                        self.current().cursor,
+                        ident.cursor,
                     );
                 }
             } else {
@@ -848,6 +972,7 @@ impl<'a> Parser<'a> {
         if self.match_tokens(vec![TokenKindDiscriminants::LBracket]) {
             let mut elements: Vec<Expr> = vec![];
 
+            self.skip_eols();
             if !self.check(TokenKindDiscriminants::RBracket) {
                 loop {
                     self.skip_eols();
@@ -856,6 +981,12 @@ impl<'a> Parser<'a> {
                     if !self.match_tokens(vec![TokenKindDiscriminants::Comma]) {
                         break;
                     }
+
+                    // allow a trailing comma before ']'
+                    self.skip_eols();
+                    if self.check(TokenKindDiscriminants::RBracket) {
+                        break;
+                    }
                 }
             }
 
@@ -875,7 +1006,8 @@ impl<'a> Parser<'a> {
         if self.match_tokens(vec![TokenKindDiscriminants::LBrace]) {
             let mut map: Vec<(Expr, Expr)> = vec![];
 
-            if !self.check(TokenKindDiscriminants::RBracket) {
+            self.skip_eols();
+            if !self.check(TokenKindDiscriminants::RBrace) {
                 loop {
                     self.skip_eols();
 
@@ -891,6 +1023,12 @@ impl<'a> Parser<'a> {
                     if !self.match_tokens(vec![TokenKindDiscriminants::Comma]) {
                         break;
                     }
+
+                    // allow a trailing comma before '}'
+                    self.skip_eols();
+                    if self.check(TokenKindDiscriminants::RBrace) {
+                        break;
+                    }
                 }
             }
 
@@ -906,6 +1044,75 @@ impl<'a> Parser<'a> {
         self.primary()
     }
 
+    fn lambda(&mut self) -> ParseResult<Expr> {
+        let fn_token = self.previous();
+        self.consume(
+            TokenKindDiscriminants::LParen,
+            "expected '(' after 'fn' in lambda",
+        )?;
+        let (params, defaults, bound) = self.fn_params()?;
+        if bound {
+            return Err(ParseErr::new(
+                "lambdas can't take 'self' as a parameter".into(),
+                fn_token.cursor,
+            ));
+        }
+
+        // `fn(x) = expr` is a shorthand for a body that returns expr
+        let body = if self.match_tokens(vec![TokenKindDiscriminants::Assign]) {
+            let val = self.expr()?;
+            let cursor = val.cursor;
+            Stmt::new(
+                StmtKind::Block(vec![Stmt::new(StmtKind::Return(Some(val)), cursor)]),
+                cursor,
+            )
+        } else {
+            self.consume_keyword(KeywordKind::Do, "expected '=' or 'do' before lambda body")?;
+            self.block_stmt()?
+        };
+
+        Ok(Expr::new(
+            ExprKind::Lambda {
+                params,
+                defaults,
+                body: Box::new(body),
+            },
+            fn_token.cursor,
+        ))
+    }
+
+    fn interpolation(&mut self) -> ParseResult<Expr> {
+        let cursor = self.previous().cursor;
+        let mut parts: Vec<Expr> = vec![];
+
+        loop {
+            let part = self.previous();
+            match &part.kind {
+                TokenKind::InterpStr(s) | TokenKind::Str(s) if !s.is_empty() => {
+                    parts.push(Expr::new(
+                        ExprKind::Literal(LiteralType::Str(s.clone())),
+                        part.cursor,
+                    ));
+                }
+                _ => {}
+            }
+            // a Str token closes the string
+            if let TokenKind::Str(_) = part.kind {
+                break;
+            }
+
+            parts.push(self.expr()?);
+            if !self.match_tokens(vec![TokenKindDiscriminants::InterpStr]) {
+                self.consume(
+                    TokenKindDiscriminants::Str,
+                    "expected '}' after interpolated expression",
+                )?;
+            }
+        }
+
+        Ok(Expr::new(ExprKind::Interp(parts), cursor))
+    }
+
     fn primary(&mut self) -> ParseResult<Expr> {
         if self.match_tokens(vec![TokenKindDiscriminants::Bool]) {
             if let TokenKind::Bool(b) = self.previous().kind {
@@ -940,6 +1147,9 @@ impl<'a> Parser<'a> {
                 ));
             }
         }
+        if self.match_tokens(vec![TokenKindDiscriminants::InterpStr]) {
+            return self.interpolation();
+        }
         if self.match_tokens(vec![TokenKindDiscriminants::LParen]) {
             let expr = self.expr()?;
             self.consume(
@@ -961,6 +1171,9 @@ impl<'a> Parser<'a> {
         if self.match_keyword(KeywordKind::KSelf) {
             return Ok(Expr::new(ExprKind::ESelf, self.previous().cursor));
         }
+        if self.match_keyword(KeywordKind::Fn) {
+            return self.lambda();
+        }
 
         Err(ParseErr::new(
             "expected expression".into(),
@@ -1085,25 +1298,116 @@ impl<'a> Parser<'a> {
 
     // Error handling functions
 
-    fn synchronize(&mut self) {
-        self.next();
+    /// Skip the whole top-level statement starting at the token `start` that failed to parse, so
+    /// parsing can continue with the next one. Blocks are tracked to find where the statement ends,
+    /// `else`, `catch` and `ensure` close the block before them like `end` does.
+    fn synchronize(&mut self, start: usize) {
+        self.curr = start;
+        let mut depth: usize = 0;
 
         while !self.is_at_end() {
-            match self.peek().kind {
-                TokenKind::Keyword(keyword) => match keyword {
-                    KeywordKind::Fn
-                    | KeywordKind::Var
-                    | KeywordKind::For
-                    | KeywordKind::If
-                    | KeywordKind::While => {
-                        break;
-                    }
-                    _ => {}
-                },
+            match self.current().kind {
+                TokenKind::EOL if depth == 0 => break,
+                TokenKind::Keyword(KeywordKind::Do) => depth += 1,
+                TokenKind::Keyword(
+                    KeywordKind::End | KeywordKind::Else | KeywordKind::Catch | KeywordKind::Ensure,
+                ) => depth = depth.saturating_sub(1),
                 _ => {}
             }
-
             self.next();
         }
+        self.skip_eols();
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::lexer::{Lexer, cursor::Cursor};
+
+    /// Lex and parse `text`
+    pub(crate) fn parse(text: &str) -> ParserOutput {
+        let mut src = Src::from_text(PathBuf::from("test.qte"), text.to_string());
+        src.tokens = Lexer::new(src.text.clone()).tokenize().tokens;
+        Parser::new(&src).parse()
+    }
+
+    fn parse_expr(text: &str) -> Expr {
+        let mut ast = parse(text).ast.expect("parser failed");
+        match ast.remove(0).kind {
+            StmtKind::Expr(expr) => expr,
+            other => panic!("expected an expression statement, found {:?}", other),
+        }
+    }
+
+    /// Render calls and member accesses as s-expressions to check the tree shape
+    fn shape(expr: &Expr) -> String {
+        match &expr.kind {
+            ExprKind::Var(name) => name.clone(),
+            ExprKind::Literal(LiteralType::Num(n)) => n.to_string(),
+            ExprKind::Get { obj, name } => format!("(get {} {})", shape(obj), name),
+            ExprKind::Call { callee, args } => {
+                let args: String = args.iter().map(|a| format!(" {}", shape(a))).collect();
+                format!("(call {}{})", shape(callee), args)
+            }
+            other => format!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn member_access() {
+        let expr = parse_expr("Math.sin(0)");
+        assert_eq!(shape(&expr), "(call (get Math sin) 0)");
+        // the member access points at the property name
+        if let ExprKind::Call { callee, .. } = &expr.kind {
+            assert_eq!(callee.cursor, Cursor { line: 0, col: 5 });
+        }
+    }
+
+    #[test]
+    fn chained_calls() {
+        assert_eq!(
+            shape(&parse_expr("a.b(1).c.d(2)")),
+            "(call (get (get (call (get a b) 1) c) d) 2)"
+        );
+        assert_eq!(shape(&parse_expr("f(1)(2)")), "(call (call f 1) 2)");
+    }
+
+    #[test]
+    fn default_params_must_trail() {
+        let ast = parse("fn f(a, b = 1, c = \"c\") do end")
+            .ast
+            .expect("parser failed");
+        match &ast[0].kind {
+            StmtKind::Fn {
+                params, defaults, ..
+            } => assert_eq!((params.len(), defaults.len()), (3, 2)),
+            other => panic!("expected a function declaration, found {:?}", other),
+        }
+        assert!(parse("fn f(a = 1, b) do end").ast.is_none());
+        assert!(parse("var f = fn(a = 1, b) = a").ast.is_none());
+    }
+
+    #[test]
+    fn reports_every_error() {
+        let out = parse("var x = )\nvar y = 1\nprintln(y +)\n");
+        assert!(out.ast.is_none());
+        let lines: Vec<usize> = out
+            .errors
+            .unwrap_or_default()
+            .iter()
+            .map(|err| err.cursor.line)
+            .collect();
+        assert_eq!(lines, vec![0, 2]);
+
+        // the rest of a block with an error is skipped instead of causing more errors
+        let out = parse(
+            "fn f(a) do\n    if a do\n        var x = (1 +\n    else do\n        println(2)\n    end\nend\ny = ]\n",
+        );
+        assert_eq!(out.error_count, 2);
+        assert!(out.ast.is_none());
     }
 }
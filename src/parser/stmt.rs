@@ -1,6 +1,8 @@
+use serde::Serialize;
+
 use crate::{lexer::cursor::Cursor, parser::expr::Expr};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum StmtKind {
     Expr(Expr),
     Return(Option<Expr>),
@@ -45,6 +47,8 @@ pub enum StmtKind {
     Fn {
         name: String,
         params: Vec<String>,
+        /// Default values of the last `defaults.len()` parameters
+        defaults: Vec<Expr>,
         body: Box<Stmt>,
         bound: bool,
     },
@@ -54,7 +58,7 @@ pub enum StmtKind {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Stmt {
     pub kind: StmtKind,
     pub cursor: Cursor,
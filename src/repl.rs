@@ -0,0 +1,139 @@
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    evaluator::{Evaluator, env::EnvPtr, natives::Natives, resolver::Resolver, value::Value},
+    lexer::{
+        Lexer,
+        token::{KeywordKind, TokenKind},
+    },
+    parser::Parser,
+    reporter::Reporter,
+    src::Src,
+};
+
+/// Interactive read-eval-print loop, globals persist between inputs
+pub fn run() {
+    println!("Quetite REPL, press Ctrl+D to exit");
+
+    let globals = Natives::get_natives();
+    let mut buf = String::new();
+
+    loop {
+        print!("{}", if buf.is_empty() { ">> " } else { ".. " });
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) => {
+                // EOF
+                println!();
+                return;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                Reporter::error(format!("failed to read input: {err}").as_str());
+                return;
+            }
+        }
+
+        buf.push_str(&line);
+        if is_incomplete(&buf) {
+            continue;
+        }
+
+        let text = std::mem::take(&mut buf);
+        if text.trim().is_empty() {
+            continue;
+        }
+        eval_input(text, globals.clone());
+    }
+}
+
+/// Run one complete input, errors are reported and don't end the session
+fn eval_input(text: String, globals: EnvPtr) {
+    let mut src = Src::from_text(PathBuf::from("<repl>"), text);
+
+    let lex_out = Lexer::new(src.text.clone()).tokenize();
+    src.tokens = match lex_out.tokens {
+        Some(toks) => Some(toks),
+        None => {
+            for err in lex_out.errors.unwrap_or_default().iter() {
+                Reporter::lex_err_at(err, &src);
+            }
+            return;
+        }
+    };
+
+    src.ast = Parser::new(&src).parse().ast;
+    if src.ast.is_none() {
+        return;
+    }
+
+    src.ast = Resolver::new(&src).resolve().ast;
+    if src.ast.is_none() {
+        return;
+    }
+
+    let mut evaluator = Evaluator::with_globals(&src, globals);
+    match evaluator.eval_repl() {
+        Ok(Some(Value::Null)) | Ok(None) | Err(_) => {}
+        Ok(Some(val)) => println!("{}", val),
+    }
+}
+
+/// Returns true if `text` has more `do` blocks opened than closed, meaning the REPL should keep
+/// reading lines before evaluating. Like in the parser, `else`, `catch` and `ensure` close the
+/// block before them, so an if/else chain is closed by a single `end`
+fn is_incomplete(text: &str) -> bool {
+    let tokens = match Lexer::new(text.to_string()).tokenize().tokens {
+        Some(toks) => toks,
+        // let lex errors be reported when the input is evaluated
+        None => return false,
+    };
+
+    let mut depth: usize = 0;
+    for token in tokens.iter() {
+        match token.kind {
+            TokenKind::Keyword(KeywordKind::Do) => depth += 1,
+            TokenKind::Keyword(
+                KeywordKind::End | KeywordKind::Else | KeywordKind::Catch | KeywordKind::Ensure,
+            ) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incomplete_blocks() {
+        assert!(!is_incomplete("var x = 1\n"));
+        assert!(is_incomplete("fn f() do\n"));
+        assert!(is_incomplete("fn f() do\nif true do\nend\n"));
+        assert!(!is_incomplete("fn f() do\nif true do\nend\nend\n"));
+        // keywords inside strings and comments don't count
+        assert!(!is_incomplete("var s = \"do\" # do\n"));
+    }
+
+    #[test]
+    fn else_shares_end() {
+        assert!(is_incomplete("if a do\n1\nelse do\n"));
+        assert!(!is_incomplete("if a do\n1\nelse do\n2\nend\n"));
+        let chain = "if a < 5 do\n1\nelse if a > 5 do\n2\nelse do\n3\n";
+        assert!(is_incomplete(chain));
+        assert!(!is_incomplete(&format!("{chain}end\n")));
+        assert!(is_incomplete("fn f() do\nif a do\n1\nelse do\n2\nend\n"));
+        assert!(!is_incomplete("try do\n1\ncatch e do\n2\nend\n"));
+        // a match with an else arm is closed by the else arm's end
+        assert!(!is_incomplete(
+            "match a do\n0 do\n1\nend\nelse do\n2\nend\n"
+        ));
+    }
+}
@@ -11,6 +11,7 @@ pub enum ReportType {
     Info,
     Warning,
     Error,
+    Note,
 }
 
 impl Display for ReportType {
@@ -19,6 +20,7 @@ impl Display for ReportType {
             ReportType::Info => "info".blue(),
             ReportType::Warning => "warning".yellow(),
             ReportType::Error => "error".red(),
+            ReportType::Note => "note".green(),
         };
         write!(f, "{str}")
     }
@@ -37,54 +39,75 @@ impl Reporter {
         found: Option<String>,
     ) {
         let _ = crossterm::terminal::disable_raw_mode();
+        print!(
+            "{}",
+            Reporter::format_at(rtype, etype, msg, src, cursor, expected, found)
+        );
+    }
+
+    /// Build the text printed by `report_at`: the message, its location and the lines around
+    /// it with a caret under the reported column
+    pub fn format_at(
+        rtype: ReportType,
+        etype: Option<String>,
+        msg: &str,
+        src: &Src,
+        cursor: Cursor,
+        expected: Option<String>,
+        found: Option<String>,
+    ) -> String {
+        let mut out = String::new();
 
         let etype_str = match etype {
             Some(s) => format!("({}) ", s),
             None => "".into(),
         };
-        println!("{}: {}{}", rtype, etype_str.red().bold(), msg.bold());
-        println!(
-            "{}{}:{}:{}:",
+        out += &format!("{}: {}{}\n", rtype, etype_str.red().bold(), msg.bold());
+        out += &format!(
+            "{}{}:{}:{}:\n",
             "--> ".blue(),
             src.file.display().to_string().blue(),
-            cursor.line.to_string().blue(),
-            cursor.col.to_string().blue(),
+            (cursor.line + 1).to_string().blue(),
+            (cursor.col + 1).to_string().blue(),
         );
 
-        let line = cursor.line;
-        if line > 0 {
-            println!(
-                "{} {} {}",
-                (line - 1).to_string().blue(),
+        // cursors are 0-based, displayed line numbers are 1-based
+        let line = cursor.line.min(src.lines.len().saturating_sub(1));
+        let has_next = line + 1 < src.lines.len();
+        // line numbers are right aligned so the gutters and the caret line up
+        let width = if has_next { line + 2 } else { line + 1 }.to_string().len();
+        let numbered = |n: usize| {
+            format!(
+                "{} {} {}\n",
+                format!("{:>width$}", n + 1).blue(),
                 "|".blue(),
-                src.lines[line - 1]
-            );
+                src.lines.get(n).map(String::as_str).unwrap_or("")
+            )
+        };
+
+        if line > 0 {
+            out += &numbered(line - 1);
         }
-        println!(
-            "{} {} {}",
-            line.to_string().blue(),
-            "|".blue(),
-            src.lines[line]
+        out += &numbered(line);
+        out += &format!(
+            "{}{}",
+            " ".repeat(width + 3 + cursor.col),
+            "^ here: ".yellow()
         );
-        print!(" {}{}", " ".repeat(cursor.col), "^ here: ".yellow());
         if let Some(estr) = expected {
-            print!("expected '{}'", estr);
+            out += &format!("expected '{}'", estr);
             if let Some(fstr) = found {
-                print!(", found '{}'", fstr);
+                out += &format!(", found '{}'", fstr);
             }
-            println!();
+            out += "\n";
         } else {
-            println!("{}", msg);
+            out += &format!("{}\n", msg);
         }
-        if line < src.lines.len() - 1 {
-            println!(
-                "{} {} {}",
-                (line + 1).to_string().blue(),
-                "|".blue(),
-                src.lines[line + 1]
-            );
+        if has_next {
+            out += &numbered(line + 1);
         }
-        println!();
+        out += "\n";
+        out
     }
 
     pub fn info_at(msg: &str, src: &Src, cursor: Cursor) {
@@ -109,6 +132,9 @@ impl Reporter {
             err.expected.clone(),
             err.found.clone(),
         );
+        if let Some(note) = &err.note {
+            Reporter::note(note);
+        }
     }
 
     pub fn lex_err_at(err: &LexErr, src: &Src) {
@@ -138,4 +164,54 @@ impl Reporter {
     pub fn error(msg: &str) {
         Reporter::report(ReportType::Error, msg);
     }
+
+    pub fn note(msg: &str) {
+        Reporter::report(ReportType::Note, msg);
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::{ReportType, Reporter};
+    use crate::evaluator::{
+        Evaluator,
+        runtime_err::{RuntimeErr, RuntimeEvent},
+        tests::resolved_src,
+    };
+
+    #[test]
+    fn caret_under_error_column() {
+        colored::control::set_override(false);
+
+        let lines: Vec<String> = (0..11).map(|i| format!("var a{} = {}", i, i)).collect();
+        let text = format!("{}\nvar x = a1 + \"s\"\nvar y = 2", lines.join("\n"));
+        let src = resolved_src(&text);
+        let mut evaluator = Evaluator::new(&src);
+        let Err(RuntimeEvent::Err(RuntimeErr {
+            kind, msg, cursor, ..
+        })) = evaluator.eval()
+        else {
+            panic!("expected a runtime error");
+        };
+        assert_eq!(cursor.line, 11);
+
+        let out = Reporter::format_at(
+            ReportType::Error,
+            Some(kind.to_string()),
+            &msg,
+            &src,
+            cursor,
+            None,
+            None,
+        );
+        let out: Vec<&str> = out.lines().collect();
+        assert_eq!(out[2], "11 | var a10 = 10");
+        assert_eq!(out[3], "12 | var x = a1 + \"s\"");
+        assert_eq!(out[5], "13 | var y = 2");
+        // the caret sits under the reported column of the source line
+        let caret = out[4].find('^').expect("missing caret");
+        assert_eq!(caret, "12 | ".len() + cursor.col);
+        assert!(out[4].ends_with(&format!("^ here: {}", msg)));
+    }
 }
@@ -20,6 +20,11 @@ impl Src {
             }
         };
 
+        Src::from_text(file, text)
+    }
+
+    /// Create a source from in-memory text, `file` is only used for reporting
+    pub fn from_text(file: PathBuf, text: String) -> Self {
         let lines: Vec<String> = text.split("\n").map(|s| s.to_string()).collect();
 
         Self {
@@ -0,0 +1,125 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use notify::{Event, RecursiveMode, Watcher};
+
+use crate::{Args, evaluator::natives::reset_tui, reporter::Reporter, run_src, src::Src};
+
+/// How long to wait for the rest of a save's events before running the file again, editors
+/// usually write a file in several steps
+const SETTLE_TIME: Duration = Duration::from_millis(50);
+
+/// Run `file`, then run it again every time it changes until the process is killed, errors are
+/// reported and don't end the loop
+pub(crate) fn run(file: PathBuf, args: &Args) {
+    run_once(&file, args);
+    let watched = watch(&file, None, || {
+        reset_tui();
+        Reporter::info(format!("{} changed, running it again", file.display()).as_str());
+        println!();
+        run_once(&file, args);
+        true
+    });
+    if let Err(err) = watched {
+        Reporter::error(format!("failed to watch {}: {err}", file.display()).as_str());
+        std::process::exit(1);
+    }
+}
+
+/// Call `on_change` every time `file` is modified, stops once `on_change` returns false or when
+/// `deadline` passes if there is one. The parent directory is watched rather than the file
+/// itself, so changes are still seen after an editor replaces the file
+fn watch(
+    file: &Path,
+    deadline: Option<Instant>,
+    mut on_change: impl FnMut() -> bool,
+) -> notify::Result<()> {
+    let file = fs::canonicalize(file)?;
+    let dir = file.parent().unwrap_or(Path::new("/"));
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    let touches_file = |event: &notify::Result<Event>| match event {
+        Ok(event) => {
+            (event.kind.is_modify() || event.kind.is_create()) && event.paths.contains(&file)
+        }
+        Err(_) => false,
+    };
+
+    let next_event = || match deadline {
+        Some(deadline) => rx
+            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            .ok(),
+        None => rx.recv().ok(),
+    };
+
+    while let Some(event) = next_event() {
+        if !touches_file(&event) {
+            continue;
+        }
+        // a single save can fire several events, only run the file once for all of them
+        while rx.recv_timeout(SETTLE_TIME).is_ok() {}
+        if !on_change() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Read `file` and run it once, reporting any errors
+fn run_once(file: &Path, args: &Args) {
+    match fs::read_to_string(file) {
+        Ok(text) => {
+            run_src(
+                Src::from_text(file.to_path_buf(), text),
+                args.args.clone(),
+                args,
+            );
+        }
+        Err(err) => Reporter::error(format!("failed to read {}: {err}", file.display()).as_str()),
+    }
+    reset_tui();
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs, thread,
+        time::{Duration, Instant},
+    };
+
+    use super::watch;
+
+    #[test]
+    fn modification_triggers_callback() {
+        let file = std::env::temp_dir().join(format!("quetite-watch-{}.qte", std::process::id()));
+        fs::write(&file, "println(1)").unwrap();
+
+        let writer = {
+            let file = file.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(200));
+                fs::write(&file, "println(1)\nprintln(2)").unwrap();
+            })
+        };
+
+        // give up instead of hanging if the change is never reported
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut changes = 0;
+        watch(&file, Some(deadline), || {
+            changes += 1;
+            false
+        })
+        .unwrap();
+        writer.join().unwrap();
+        fs::remove_file(&file).unwrap();
+        assert_eq!(changes, 1);
+    }
+}
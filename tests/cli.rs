@@ -0,0 +1,182 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+fn queitite(args: &[&str]) -> (String, String, bool) {
+    queitite_with_stdin(args, "")
+}
+
+fn queitite_with_stdin(args: &[&str], stdin: &str) -> (String, String, bool) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_queitite"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run queitite");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let out = child.wait_with_output().expect("failed to run queitite");
+    (
+        String::from_utf8_lossy(&out.stdout).to_string(),
+        String::from_utf8_lossy(&out.stderr).to_string(),
+        out.status.success(),
+    )
+}
+
+#[test]
+fn eval_prints() {
+    let (stdout, _, ok) = queitite(&["-e", "println(\"hi\")"]);
+    assert!(ok);
+    assert_eq!(stdout, "hi\n");
+}
+
+#[test]
+fn print_multiple_args() {
+    let (stdout, _, ok) = queitite(&["-e", "println(\"a\", 1, true)\nprint(1, 2)\nprintln()"]);
+    assert!(ok);
+    assert_eq!(stdout, "a 1 true\n1 2\n");
+}
+
+#[test]
+fn eprint_writes_to_stderr() {
+    let (stdout, stderr, ok) = queitite(&[
+        "-e",
+        "eprintln(\"oops\", 1)\neprint(\"a\")\nprintln(\"data\")",
+    ]);
+    assert!(ok);
+    assert_eq!(stdout, "data\n");
+    assert_eq!(stderr, "oops 1\na");
+}
+
+#[test]
+fn eval_args() {
+    let (stdout, _, ok) = queitite(&["--eval", "println(Sys.args())", "foo", "bar"]);
+    assert!(ok);
+    assert_eq!(stdout, "[\"foo\", \"bar\"]\n");
+}
+
+#[test]
+fn eval_errors_use_eval_filename() {
+    let (stdout, _, ok) = queitite(&["-e", "var x = 1 + \"a\""]);
+    assert!(!ok);
+    assert!(stdout.contains("<eval>:1:"), "{stdout}");
+}
+
+#[test]
+fn trace_prints_globals() {
+    let (_, stderr, ok) = queitite(&["--trace", "-e", "var y = 2\nvar x = 1\nx = x + y"]);
+    assert!(ok);
+    assert_eq!(
+        stderr,
+        "[trace] line 1\n  y = 2\n\
+         [trace] line 2\n  x = 1\n  y = 2\n\
+         [trace] line 3\n  x = 3\n  y = 2\n"
+    );
+}
+
+#[test]
+fn read_without_prompt() {
+    let (stdout, _, ok) = queitite_with_stdin(&["-e", "println(read() + \"!\")"], "hello\n");
+    assert!(ok);
+    assert_eq!(stdout, "hello!\n");
+}
+
+#[test]
+fn read_with_prompt() {
+    let (stdout, _, ok) = queitite_with_stdin(
+        &["-e", "var name = read(\"name: \")\nprintln(\"hi \" + name)"],
+        "bob\n",
+    );
+    assert!(ok);
+    assert_eq!(stdout, "name: hi bob\n");
+}
+
+/// Write `files` into a fresh temporary directory and return its path
+fn temp_scripts(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("quetite-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    for (file, text) in files {
+        std::fs::write(dir.join(file), text).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn use_loads_relative_scripts() {
+    let dir = temp_scripts(
+        "use_relative",
+        &[
+            (
+                "main.qte",
+                "use \"lib/greet.qte\"\nprintln(greet(\"bob\"))\n",
+            ),
+            (
+                "lib/greet.qte",
+                "use \"name.qte\"\nfn greet(who) do\n    return prefix + who\nend\n",
+            ),
+            ("lib/name.qte", "var prefix = \"hi \"\n"),
+        ],
+    );
+    let (stdout, _, ok) = queitite(&[dir.join("main.qte").to_str().unwrap()]);
+    assert!(ok, "{stdout}");
+    assert_eq!(stdout, "hi bob\n");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn circular_use_is_an_error() {
+    let dir = temp_scripts(
+        "use_circular",
+        &[("a.qte", "use \"b.qte\"\n"), ("b.qte", "use \"a.qte\"\n")],
+    );
+    let (stdout, _, ok) = queitite(&[dir.join("a.qte").to_str().unwrap()]);
+    assert!(!ok);
+    assert!(stdout.contains("circular use of"), "{stdout}");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn halt_in_used_script_stops_program() {
+    let dir = temp_scripts(
+        "use_halt",
+        &[
+            ("main.qte", "use \"lib/stop.qte\"\nprintln(\"after\")\n"),
+            ("lib/stop.qte", "println(\"before\")\nhalt()\n"),
+        ],
+    );
+    let (stdout, _, ok) = queitite(&[dir.join("main.qte").to_str().unwrap()]);
+    assert!(ok, "{stdout}");
+    assert_eq!(stdout, "before\n");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn input_available_polls_stdin() {
+    let script = "Sys.sleep(200)\nprintln(Sys.input_available())";
+
+    let (stdout, _, ok) = queitite_with_stdin(&["-e", script], "hello\n");
+    assert!(ok);
+    assert_eq!(stdout, "true\n");
+
+    // stdin stays open with nothing written to it
+    let mut child = Command::new(env!("CARGO_BIN_EXE_queitite"))
+        .args(["-e", "println(Sys.input_available())"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run queitite");
+    let stdin = child.stdin.take();
+    let out = child.wait_with_output().expect("failed to run queitite");
+    drop(stdin);
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "false\n");
+}